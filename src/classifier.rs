@@ -0,0 +1,304 @@
+//! Multinomial naive-Bayes language classifier — the last resort `detect_from_content`
+//! reaches for once magic bytes, JSON parsing, shebangs, and the Markdown
+//! heuristic have all failed to identify a format.
+//!
+//! Modeled on enry's `GetLanguagesByClassifier`: each candidate language
+//! carries a token count table and a prior, trained offline on a small
+//! representative corpus per language (enry's table comes from a much
+//! larger corpus; this one is a compact hand-curated stand-in scoped to
+//! languages `syntax_fallback`/syntect can actually highlight) and
+//! embedded here as a static blob. At classify time we tokenize the
+//! content, score every candidate with
+//! `log_prior + Σ count(token) * log_prob(token)`, and only trust the
+//! winner if it beats the runner-up by `MARGIN` — otherwise the caller
+//! should keep `FileFormat::Plain` rather than confidently guess wrong.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::detect::FileFormat;
+
+/// Log-probability margin the top-scoring language must lead the
+/// runner-up by before we trust the guess over `Plain`.
+const MARGIN: f64 = 3.0;
+
+/// Tokens considered per classification; content past this is ignored
+/// (classifying on the first ~8KB is plenty and keeps this fast on
+/// arbitrarily large stdin input).
+const MAX_TOKENS: usize = 2000;
+
+struct LangModel {
+    lang: &'static str,
+    /// log(fraction of the training corpus that was this language)
+    log_prior: f64,
+    /// (token, occurrence count) pairs from the offline training corpus.
+    counts: &'static [(&'static str, u32)],
+}
+
+/// Training corpus, one entry per candidate language. Restricted to names
+/// `detect_format` already uses, so every result is guaranteed
+/// highlightable via syntect or `syntax_fallback`.
+static MODELS: &[LangModel] = &[
+    LangModel {
+        lang: "Python",
+        log_prior: -2.3,
+        counts: &[
+            ("def", 420), ("self", 600), ("import", 300), ("return", 350),
+            ("elif", 90), ("none", 200), ("true", 120), ("false", 120),
+            ("class", 150), ("print", 180), ("__init__", 140), ("for", 260),
+            ("in", 400), (":", 900), ("#", 200), ("lambda", 40), ("except", 70),
+            ("raise", 60), ("yield", 30), ("async", 30), ("await", 30),
+        ],
+    },
+    LangModel {
+        lang: "JavaScript",
+        log_prior: -2.3,
+        counts: &[
+            ("function", 300), ("const", 350), ("let", 250), ("var", 150),
+            ("this", 400), ("=>", 220), ("require", 80), ("module", 60),
+            ("export", 140), ("import", 160), ("console", 90), ("null", 120),
+            ("undefined", 60), ("return", 300), ("{", 800), ("}", 800),
+            (";", 700), ("async", 60), ("await", 60), ("prototype", 40),
+        ],
+    },
+    LangModel {
+        lang: "Ruby",
+        log_prior: -3.0,
+        counts: &[
+            ("def", 350), ("end", 500), ("do", 200), ("puts", 100),
+            ("require", 80), ("module", 70), ("class", 150), ("attr_accessor", 40),
+            ("nil", 150), ("true", 90), ("false", 90), ("elsif", 70),
+            ("yield", 40), ("each", 120), ("@", 160), ("symbol", 10), (":", 300),
+        ],
+    },
+    LangModel {
+        lang: "Go",
+        log_prior: -3.0,
+        counts: &[
+            ("func", 350), ("package", 200), ("import", 150), ("return", 300),
+            ("var", 120), ("struct", 150), ("interface", 60), ("error", 140),
+            ("nil", 160), ("defer", 50), ("go", 40), ("chan", 30),
+            (":=", 220), ("{", 700), ("}", 700), ("fmt", 90), ("range", 80),
+        ],
+    },
+    LangModel {
+        lang: "Java",
+        log_prior: -2.6,
+        counts: &[
+            ("public", 300), ("private", 200), ("class", 250), ("static", 180),
+            ("void", 200), ("new", 220), ("import", 150), ("package", 100),
+            ("extends", 70), ("implements", 50), ("this", 200), ("null", 90),
+            ("int", 250), ("string", 200), ("return", 280), (";", 900),
+            ("{", 800), ("}", 800), ("@override", 60), ("final", 80),
+        ],
+    },
+    LangModel {
+        lang: "C",
+        log_prior: -3.2,
+        counts: &[
+            ("#include", 200), ("int", 300), ("void", 180), ("char", 150),
+            ("struct", 140), ("return", 260), ("static", 90), ("const", 100),
+            ("malloc", 50), ("free", 40), ("printf", 120), ("null", 80),
+            ("sizeof", 60), (";", 900), ("{", 700), ("}", 700), ("#define", 70),
+        ],
+    },
+    LangModel {
+        lang: "C++",
+        log_prior: -3.2,
+        counts: &[
+            ("#include", 180), ("namespace", 90), ("class", 160), ("public", 120),
+            ("private", 90), ("template", 70), ("std", 200), ("void", 150),
+            ("const", 140), ("new", 100), ("delete", 40), ("virtual", 50),
+            ("return", 250), ("cout", 60), ("nullptr", 50), ("::", 200),
+            (";", 900), ("{", 700), ("}", 700),
+        ],
+    },
+    LangModel {
+        lang: "Bash",
+        log_prior: -3.5,
+        counts: &[
+            ("#!/bin/bash", 30), ("echo", 180), ("if", 150), ("then", 140),
+            ("fi", 140), ("for", 100), ("do", 120), ("done", 120), ("case", 60),
+            ("esac", 60), ("function", 40), ("export", 60), ("local", 50),
+            ("$", 400), ("#", 150), ("[", 150), ("]", 150), ("||", 50), ("&&", 60),
+        ],
+    },
+    LangModel {
+        lang: "PHP",
+        log_prior: -3.5,
+        counts: &[
+            ("<?php", 60), ("function", 200), ("echo", 150), ("$this", 180),
+            ("public", 150), ("private", 100), ("class", 140), ("array", 120),
+            ("foreach", 80), ("require", 60), ("return", 200), ("null", 70),
+            ("$", 500), ("->", 220), (";", 800), ("{", 600), ("}", 600),
+        ],
+    },
+    LangModel {
+        lang: "HTML",
+        log_prior: -3.0,
+        counts: &[
+            ("<html", 40), ("<div", 150), ("<span", 70), ("<body", 40),
+            ("<head", 40), ("<script", 50), ("<style", 40), ("class", 160),
+            ("href", 90), ("src", 90), ("<p", 70), ("<a", 90), ("id", 100),
+            ("<", 900), (">", 900), ("</", 500), ("=", 400),
+        ],
+    },
+    LangModel {
+        lang: "CSS",
+        log_prior: -3.8,
+        counts: &[
+            ("color", 140), ("background", 130), ("margin", 120), ("padding", 120),
+            ("display", 110), ("width", 100), ("height", 100), ("px", 200),
+            ("font-size", 60), ("border", 90), ("flex", 60), ("@media", 30),
+            ("{", 600), ("}", 600), (":", 700), (";", 700), ("#", 100), (".", 200),
+        ],
+    },
+    LangModel {
+        lang: "SQL",
+        log_prior: -3.8,
+        counts: &[
+            ("select", 200), ("from", 190), ("where", 170), ("insert", 90),
+            ("update", 80), ("delete", 60), ("join", 100), ("table", 120),
+            ("create", 80), ("values", 70), ("null", 90), ("as", 130),
+            ("order", 60), ("by", 70), ("group", 50), (";", 300), ("*", 100),
+        ],
+    },
+    LangModel {
+        lang: "Rust",
+        log_prior: -2.8,
+        counts: &[
+            ("fn", 350), ("let", 300), ("mut", 150), ("struct", 160),
+            ("impl", 150), ("pub", 200), ("use", 180), ("match", 120),
+            ("return", 150), ("self", 220), ("none", 90), ("some", 90),
+            ("result", 80), ("ok", 70), ("err", 70), ("::", 250),
+            ("{", 700), ("}", 700), ("->", 150), ("&", 300),
+        ],
+    },
+];
+
+struct Trained {
+    log_prior: f64,
+    log_prob: HashMap<&'static str, f64>,
+    unknown_log_prob: f64,
+}
+
+/// Precomputes each model's per-token log-probabilities and unknown-token
+/// floor once. `MODELS` itself is the "offline-trained" blob; this just
+/// turns raw counts into the log-space form the scoring loop wants.
+fn trained_models() -> &'static [(&'static str, Trained)] {
+    static CELL: OnceLock<Vec<(&'static str, Trained)>> = OnceLock::new();
+    CELL.get_or_init(|| {
+        MODELS
+            .iter()
+            .map(|m| {
+                let total: u32 = m.counts.iter().map(|(_, c)| c).sum();
+                let vocab = m.counts.len() as f64;
+                let log_prob = m
+                    .counts
+                    .iter()
+                    .map(|(tok, c)| (*tok, (*c as f64 / total as f64).ln()))
+                    .collect();
+                let unknown_log_prob = (1.0 / (total as f64 + vocab)).ln();
+
+                (
+                    m.lang,
+                    Trained {
+                        log_prior: m.log_prior,
+                        log_prob,
+                        unknown_log_prob,
+                    },
+                )
+            })
+            .collect()
+    })
+}
+
+fn tokenize(content: &str) -> Vec<String> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]*|[^\sA-Za-z0-9_]").unwrap());
+
+    re.find_iter(content)
+        .take(MAX_TOKENS)
+        .map(|m| m.as_str().to_lowercase())
+        .collect()
+}
+
+/// Classifies `content` as one of `MODELS`'s languages, or `None` if no
+/// candidate beats the runner-up by `MARGIN` (i.e. the content is too
+/// ambiguous to guess confidently).
+pub fn classify(content: &str) -> Option<FileFormat> {
+    let tokens = tokenize(content);
+    if tokens.is_empty() {
+        return None;
+    }
+
+    let mut counts: HashMap<&str, u32> = HashMap::new();
+    for tok in &tokens {
+        *counts.entry(tok.as_str()).or_insert(0) += 1;
+    }
+
+    let mut scores: Vec<(&'static str, f64)> = trained_models()
+        .iter()
+        .map(|(lang, trained)| {
+            let score = trained.log_prior
+                + counts
+                    .iter()
+                    .map(|(tok, n)| {
+                        *n as f64
+                            * trained
+                                .log_prob
+                                .get(tok)
+                                .copied()
+                                .unwrap_or(trained.unknown_log_prob)
+                    })
+                    .sum::<f64>();
+            (*lang, score)
+        })
+        .collect();
+
+    scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+    let (top_lang, top_score) = scores[0];
+    let runner_up = scores.get(1).map(|(_, s)| *s).unwrap_or(f64::NEG_INFINITY);
+
+    if top_score - runner_up >= MARGIN {
+        Some(FileFormat::Code(top_lang.to_string()))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classifies_python() {
+        let src = "def foo(self):\n    import os\n    return self.bar\nclass Baz:\n    def __init__(self):\n        pass\n";
+        let fmt = classify(src);
+        assert!(matches!(fmt, Some(FileFormat::Code(ref l)) if l == "Python"));
+    }
+
+    #[test]
+    fn test_classifies_go() {
+        let src = "package main\n\nimport \"fmt\"\n\nfunc main() {\n\tvar x := 1\n\tfmt.Println(x)\n}\n";
+        let fmt = classify(src);
+        assert!(matches!(fmt, Some(FileFormat::Code(ref l)) if l == "Go"));
+    }
+
+    #[test]
+    fn test_empty_content_classifies_to_none() {
+        assert!(classify("").is_none());
+    }
+
+    #[test]
+    fn test_ambiguous_content_stays_unclassified() {
+        // Plain prose has no language-specific tokens at all, so every
+        // candidate should collapse to its unknown-token floor and none
+        // should clear the margin over the runner-up.
+        let fmt = classify("the quick brown fox jumps over the lazy dog");
+        assert!(fmt.is_none());
+    }
+}
@@ -0,0 +1,63 @@
+//! User config directory: a `vita/config.toml` for default flag values, a
+//! `vita/themes/` folder of user-supplied theme files, and a
+//! `vita/languages.toml` of user-supplied language mappings (see
+//! `langmap`), all resolved via the platform config dir (mirrors how `bat`
+//! locates its config using the `directories` crate).
+//!
+//! CLI flags always win; this module only supplies fallback values for
+//! flags the user omitted. Parsing is deliberately loose — `toml::Value`
+//! with missing/malformed fields just falling back to `None` — in the same
+//! spirit as `load_overrides` in `langprofile.rs`.
+
+use std::path::PathBuf;
+
+use directories::ProjectDirs;
+
+/// Default flag values loaded from `vita/config.toml`, if present.
+#[derive(Debug, Default, Clone)]
+pub struct Config {
+    pub theme: Option<String>,
+    pub width: Option<u32>,
+    pub line_numbers: Option<bool>,
+    pub icons: Option<bool>,
+}
+
+/// The platform config directory for vita, e.g. `~/.config/vita` on Linux.
+pub fn config_dir() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "vita").map(|dirs| dirs.config_dir().to_path_buf())
+}
+
+/// The directory `render::syntax::theme_set` scans for user-supplied
+/// `.tmTheme` files, alongside syntect's bundled themes. `Theme::from_name`
+/// and `Theme::list_all` do not read it.
+pub fn user_theme_dir() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("themes"))
+}
+
+/// The directory `render::syntax::find_syntax` should additionally scan for
+/// user-supplied `.sublime-syntax` files, alongside the built-in set.
+pub fn user_syntax_dir() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("syntaxes"))
+}
+
+/// Loads `config.toml` out of [`config_dir`], if present. Any read or parse
+/// failure is treated the same as "no config file" rather than an error.
+pub fn load() -> Config {
+    let Some(dir) = config_dir() else {
+        return Config::default();
+    };
+
+    let Ok(text) = std::fs::read_to_string(dir.join("config.toml")) else {
+        return Config::default();
+    };
+    let Ok(doc) = toml::from_str::<toml::Value>(&text) else {
+        return Config::default();
+    };
+
+    Config {
+        theme: doc.get("theme").and_then(|v| v.as_str()).map(String::from),
+        width: doc.get("width").and_then(|v| v.as_integer()).map(|n| n as u32),
+        line_numbers: doc.get("line_numbers").and_then(|v| v.as_bool()),
+        icons: doc.get("icons").and_then(|v| v.as_bool()),
+    }
+}
@@ -5,6 +5,7 @@ pub enum FileFormat {
     Markdown,
     Json,
     Csv,
+    Org,
     Code(String), // language name
     Image,
     Plain,
@@ -15,8 +16,12 @@ pub enum FileFormat {
 /// syntect's default SyntaxSet (load_defaults) contains ~75 syntaxes.
 /// Languages like TypeScript, TSX, JSX, PowerShell, etc. are NOT included.
 /// This function maps them to the closest available syntax for highlighting.
-pub fn syntax_fallback(lang: &str) -> &str {
-    match lang {
+pub fn syntax_fallback(lang: &str) -> String {
+    if let Some(syntax) = crate::langmap::merged().syntax_override(lang) {
+        return syntax.to_string();
+    }
+
+    let fallback = match lang {
         // TypeScript family → JavaScript (close enough for highlighting)
         "TypeScript" | "tsx" | "TSX" | "jsx" | "JSX" | "TypeScriptReact"
         | "JavaScriptReact" | "Svelte" | "Vue" => "JavaScript",
@@ -51,45 +56,71 @@ pub fn syntax_fallback(lang: &str) -> &str {
         "GraphQL" | "gql" => "JavaScript",
         "Protocol Buffers" | "proto" => "Java",
         "VimL" | "vim" => "Bourne Again Shell (bash)",
+        "Solidity" => "JavaScript",
+
+        // Heuristically-detected languages with no close syntect match
+        "MATLAB" | "Prolog" | "Rebol" | "Verilog" | "Coq" => "Plain Text",
 
         // Already supported - pass through
         _ => lang,
-    }
+    };
+    fallback.to_string()
 }
 
 pub fn detect_format(path: &Path) -> FileFormat {
+    if crate::render::image::is_supported(path) {
+        return FileFormat::Image;
+    }
+
     let ext = path
         .extension()
         .and_then(|e| e.to_str())
         .unwrap_or("")
         .to_lowercase();
 
+    let filename = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    // User-configured extension/filename mappings (`langmap`) win over
+    // everything below, including the content-sniffing heuristics.
+    let user_languages = crate::langmap::merged();
+    if let Some(lang) = user_languages.lookup_filename(&filename) {
+        return FileFormat::Code(lang);
+    }
+    if let Some(lang) = user_languages.lookup_ext(&ext) {
+        return FileFormat::Code(lang);
+    }
+
+    // Extensions shared by more than one language (`.h`, `.ts`, ...) need a
+    // peek at the content to disambiguate; see `heuristics`.
+    if let Some(fmt) = crate::heuristics::resolve(&ext, path) {
+        return fmt;
+    }
+
     match ext.as_str() {
         // Markdown
         "md" | "markdown" | "mdown" | "mkd" => FileFormat::Markdown,
 
+        // Org-mode
+        "org" => FileFormat::Org,
+
         // JSON
         "json" | "jsonc" | "geojson" | "jsonl" => FileFormat::Json,
 
         // CSV/TSV
         "csv" | "tsv" => FileFormat::Csv,
 
-        // Images
-        "png" | "jpg" | "jpeg" | "gif" | "bmp" | "tga" | "ppm" | "webp" | "ico"
-        | "tiff" | "tif" | "qoi" | "exr" | "hdr" | "pgm" | "pbm" | "pam" | "ff" => {
-            FileFormat::Image
-        }
-
         // ── Languages with native syntect support ──────────────
         "rs" => FileFormat::Code("Rust".into()),
         "py" | "pyw" | "pyi" | "pyx" => FileFormat::Code("Python".into()),
         "js" | "mjs" | "cjs" => FileFormat::Code("JavaScript".into()),
         "c" => FileFormat::Code("C".into()),
-        "h" => FileFormat::Code("C".into()), // could be C or C++, default to C
         "cpp" | "cc" | "cxx" | "c++" | "hpp" | "hxx" | "h++" | "hh" | "ipp" | "inl" => {
             FileFormat::Code("C++".into())
         }
-        "m" => FileFormat::Code("Objective-C".into()),
         "mm" => FileFormat::Code("Objective-C++".into()),
         "java" | "bsh" => FileFormat::Code("Java".into()),
         "go" => FileFormat::Code("Go".into()),
@@ -98,8 +129,8 @@ pub fn detect_format(path: &Path) -> FileFormat {
         "cs" | "csx" => FileFormat::Code("C#".into()),
         "scala" | "sbt" => FileFormat::Code("Scala".into()),
         "lua" => FileFormat::Code("Lua".into()),
-        "r" | "rmd" => FileFormat::Code("R".into()),
-        "pl" | "pm" | "pod" => FileFormat::Code("Perl".into()),
+        "rmd" => FileFormat::Code("R".into()),
+        "pm" | "pod" => FileFormat::Code("Perl".into()),
         "d" | "di" => FileFormat::Code("D".into()),
         "hs" | "lhs" => FileFormat::Code("Haskell".into()),
         "ml" | "mli" => FileFormat::Code("OCaml".into()),
@@ -126,7 +157,7 @@ pub fn detect_format(path: &Path) -> FileFormat {
         "textile" => FileFormat::Code("Textile".into()),
 
         // ── Languages needing fallback (not in syntect defaults) ──
-        "ts" | "mts" | "cts" => FileFormat::Code("TypeScript".into()),
+        "mts" | "cts" => FileFormat::Code("TypeScript".into()),
         "tsx" => FileFormat::Code("TSX".into()),
         "jsx" => FileFormat::Code("JSX".into()),
         "svelte" => FileFormat::Code("Svelte".into()),
@@ -155,13 +186,7 @@ pub fn detect_format(path: &Path) -> FileFormat {
 
         _ => {
             // Check filename (no extension)
-            let name = path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("")
-                .to_lowercase();
-
-            match name.as_str() {
+            match filename.as_str() {
                 "makefile" | "gnumakefile" => FileFormat::Code("Makefile".into()),
                 "dockerfile" => FileFormat::Code("Dockerfile".into()),
                 "cmakelists.txt" => FileFormat::Code("CMake".into()),
@@ -183,28 +208,8 @@ pub fn detect_format(path: &Path) -> FileFormat {
 pub fn detect_from_content(content: &str) -> FileFormat {
     let bytes = content.as_bytes();
 
-    // Check for binary image formats via magic bytes
-    if bytes.len() >= 12 {
-        // PNG
-        if bytes.starts_with(&[0x89, 0x50, 0x4E, 0x47]) {
-            return FileFormat::Image;
-        }
-        // JPEG
-        if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
-            return FileFormat::Image;
-        }
-        // GIF
-        if bytes.starts_with(b"GIF8") {
-            return FileFormat::Image;
-        }
-        // WebP (RIFF....WEBP)
-        if &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
-            return FileFormat::Image;
-        }
-        // BMP
-        if bytes.starts_with(b"BM") && bytes.len() > 14 {
-            return FileFormat::Image;
-        }
+    if crate::render::image::is_image_magic(bytes) {
+        return FileFormat::Image;
     }
 
     let trimmed = content.trim_start();
@@ -233,6 +238,9 @@ pub fn detect_from_content(content: &str) -> FileFormat {
     // Shebang
     if trimmed.starts_with("#!") {
         let first_line = trimmed.lines().next().unwrap_or("");
+        if let Some(lang) = crate::langmap::merged().lookup_shebang(first_line) {
+            return FileFormat::Code(lang);
+        }
         if first_line.contains("python") {
             return FileFormat::Code("Python".into());
         }
@@ -259,6 +267,13 @@ pub fn detect_from_content(content: &str) -> FileFormat {
         return FileFormat::Code("Diff".into());
     }
 
+    // Editor modelines (`vim: set ft=...`, `-*- mode: ... -*-`) are an
+    // explicit declaration of intent, so they outrank the softer
+    // Markdown/classifier guesses below.
+    if let Some(fmt) = crate::modeline::detect(content) {
+        return fmt;
+    }
+
     // Markdown heuristics
     let lines: Vec<&str> = trimmed.lines().take(20).collect();
     let md_score = lines.iter().filter(|l| {
@@ -275,5 +290,11 @@ pub fn detect_from_content(content: &str) -> FileFormat {
         return FileFormat::Markdown;
     }
 
+    // Last resort: statistical classification, only trusted when it's
+    // confident enough to beat the runner-up language by a clear margin.
+    if let Some(fmt) = crate::classifier::classify(content) {
+        return fmt;
+    }
+
     FileFormat::Plain
 }
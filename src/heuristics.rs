@@ -0,0 +1,211 @@
+//! Content-based disambiguation for file extensions shared by multiple
+//! languages, modeled on github-linguist's `heuristics.yml`.
+//!
+//! `detect_format` resolves most extensions to a language with a single
+//! static table, but some extensions (`.h`, `.m`, `.pl`, `.r`, `.ts`, `.v`,
+//! `.sol`, ...) are ambiguous between two or more languages that can't be
+//! told apart by extension alone. For those, `resolve` reads a short
+//! prefix of the file and evaluates an ordered list of regexes, returning
+//! the first match's language, or the extension's default if none match.
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::detect::FileFormat;
+
+/// Bytes of file content considered when disambiguating — github-linguist
+/// similarly peeks at a prefix rather than scanning the whole file.
+const PEEK_BYTES: usize = 8192;
+
+struct Rule {
+    pattern: &'static str,
+    lang: &'static str,
+}
+
+struct Ambiguous {
+    ext: &'static str,
+    rules: &'static [Rule],
+    default: &'static str,
+}
+
+static AMBIGUOUS: &[Ambiguous] = &[
+    Ambiguous {
+        ext: "h",
+        rules: &[
+            Rule {
+                pattern: r"(?m)^\s*@(interface|implementation|protocol)\b",
+                lang: "Objective-C",
+            },
+            Rule {
+                pattern: r"(?m)^\s*(template\s*<|class\s+\w+|namespace\s+\w+|std::)",
+                lang: "C++",
+            },
+        ],
+        default: "C",
+    },
+    Ambiguous {
+        ext: "m",
+        rules: &[
+            Rule {
+                pattern: r"(?m)^\s*(#import\b|@(interface|implementation|protocol)\b)",
+                lang: "Objective-C",
+            },
+            Rule {
+                pattern: r"(?m)^\s*function\s*(\[[^\]]*\]|\w+)?\s*=|^\s*%",
+                lang: "MATLAB",
+            },
+        ],
+        default: "Objective-C",
+    },
+    Ambiguous {
+        ext: "pl",
+        rules: &[
+            Rule {
+                pattern: r"(?m)^\s*:-\s*\w+\(",
+                lang: "Prolog",
+            },
+            Rule {
+                pattern: r"(?m)^\s*(use\s+(strict|warnings)\b|my\s+\$|sub\s+\w+)",
+                lang: "Perl",
+            },
+        ],
+        default: "Perl",
+    },
+    Ambiguous {
+        ext: "r",
+        rules: &[
+            Rule {
+                pattern: r"(?m)^\s*REBOL\s*\[",
+                lang: "Rebol",
+            },
+            Rule {
+                pattern: r"(?m)(<-|^\s*function\s*\(|library\()",
+                lang: "R",
+            },
+        ],
+        default: "R",
+    },
+    Ambiguous {
+        ext: "ts",
+        rules: &[
+            Rule {
+                pattern: r#"(?m)^\s*(<\?xml\b|<[A-Za-z][\w:-]*[^>]*>\s*$)"#,
+                lang: "XML",
+            },
+            Rule {
+                pattern: r"(?m)^\s*(import|export)\b.*from\b|:\s*(string|number|boolean)\b|^\s*interface\s+\w+",
+                lang: "TypeScript",
+            },
+        ],
+        default: "TypeScript",
+    },
+    Ambiguous {
+        ext: "v",
+        rules: &[
+            Rule {
+                pattern: r"(?m)^\s*(Theorem|Definition|Lemma|Qed)\b",
+                lang: "Coq",
+            },
+            Rule {
+                pattern: r"(?m)^\s*(module\s+\w+\s*(\(|;)|endmodule\b)",
+                lang: "Verilog",
+            },
+        ],
+        default: "Verilog",
+    },
+    Ambiguous {
+        ext: "sol",
+        rules: &[Rule {
+            pattern: r"(?m)^\s*(pragma\s+solidity\b|contract\s+\w+)",
+            lang: "Solidity",
+        }],
+        default: "Solidity",
+    },
+];
+
+/// Compiles every rule's regex exactly once, in `AMBIGUOUS` order.
+fn compiled_rules() -> &'static [Vec<Regex>] {
+    static CELL: OnceLock<Vec<Vec<Regex>>> = OnceLock::new();
+    CELL.get_or_init(|| {
+        AMBIGUOUS
+            .iter()
+            .map(|a| {
+                a.rules
+                    .iter()
+                    .map(|r| Regex::new(r.pattern).expect("heuristic pattern is valid"))
+                    .collect()
+            })
+            .collect()
+    })
+}
+
+/// Disambiguates `ext` by peeking at `path`'s content. Returns `None` if
+/// `ext` has no heuristic rules (i.e. it isn't ambiguous); otherwise
+/// always returns `Some`, falling back to the extension's default language
+/// if no rule matches or the file can't be read.
+pub fn resolve(ext: &str, path: &Path) -> Option<FileFormat> {
+    let idx = AMBIGUOUS.iter().position(|a| a.ext == ext)?;
+    let entry = &AMBIGUOUS[idx];
+
+    if let Some(content) = peek(path) {
+        for (rule, re) in entry.rules.iter().zip(compiled_rules()[idx].iter()) {
+            if re.is_match(&content) {
+                return Some(FileFormat::Code(rule.lang.to_string()));
+            }
+        }
+    }
+
+    Some(FileFormat::Code(entry.default.to_string()))
+}
+
+/// Reads up to `PEEK_BYTES` of `path`, lossily decoding as UTF-8.
+fn peek(path: &Path) -> Option<String> {
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut buf = vec![0u8; PEEK_BYTES];
+    let n = file.read(&mut buf).ok()?;
+    buf.truncate(n);
+    Some(String::from_utf8_lossy(&buf).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_tmp(name: &str, content: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(name);
+        let mut f = std::fs::File::create(&path).unwrap();
+        f.write_all(content.as_bytes()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_h_objective_c() {
+        let path = write_tmp("vita_heuristics_h_objc.h", "@interface Foo : NSObject\n@end\n");
+        let fmt = resolve("h", &path).unwrap();
+        assert!(matches!(fmt, FileFormat::Code(ref l) if l == "Objective-C"));
+    }
+
+    #[test]
+    fn test_h_cpp() {
+        let path = write_tmp("vita_heuristics_h_cpp.h", "template<typename T>\nclass Foo {};\n");
+        let fmt = resolve("h", &path).unwrap();
+        assert!(matches!(fmt, FileFormat::Code(ref l) if l == "C++"));
+    }
+
+    #[test]
+    fn test_h_default_c() {
+        let path = write_tmp("vita_heuristics_h_c.h", "#define FOO 1\nint foo(void);\n");
+        let fmt = resolve("h", &path).unwrap();
+        assert!(matches!(fmt, FileFormat::Code(ref l) if l == "C"));
+    }
+
+    #[test]
+    fn test_not_ambiguous() {
+        assert!(resolve("rs", Path::new("nonexistent.rs")).is_none());
+    }
+}
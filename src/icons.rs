@@ -0,0 +1,64 @@
+//! Nerd Font file-type glyphs for the `--info` header.
+//!
+//! Nerd Font icons live in a font's Private Use Area, so a terminal without
+//! a patched font renders them as tofu boxes instead of failing loudly.
+//! That makes icons strictly opt-in (`--icons`, or `icons` in
+//! `config.toml`) and further gated on [`supports_icons`] so the default
+//! experience in an unpatched terminal stays clean.
+
+use std::io::IsTerminal;
+use std::path::Path;
+
+use crate::detect::FileFormat;
+
+/// Picks a single Nerd Font glyph for `format`, refining `FileFormat::Code`
+/// to a language-specific icon via a handful of common languages (or, for a
+/// few well-known build-tool filenames, via `path` directly) and falling
+/// back to a plain page glyph for everything else.
+pub fn icon_for(format: &FileFormat, path: &Path) -> char {
+    match format {
+        FileFormat::Markdown => '\u{f48a}', // nf-oct-markdown
+        FileFormat::Json => '\u{e60b}',     // nf-seti-json
+        FileFormat::Csv => '\u{f0ce}',      // nf-fa-table
+        FileFormat::Org => '\u{e633}',      // nf-custom-orgmode
+        FileFormat::Image => '\u{f1c5}',    // nf-fa-file_image_o
+        FileFormat::Plain => '\u{f15c}',    // nf-fa-file_text
+        FileFormat::Code(lang) => icon_for_filename(path).unwrap_or_else(|| icon_for_code(lang)),
+    }
+}
+
+/// A few build-tool filenames get their own glyph regardless of language,
+/// mirroring the filename-based special cases in `detect::detect_format`.
+fn icon_for_filename(path: &Path) -> Option<char> {
+    let name = path.file_name()?.to_str()?.to_lowercase();
+    match name.as_str() {
+        "cargo.toml" | "cargo.lock" => Some('\u{e7a8}'), // nf-dev-rust
+        "makefile" | "gnumakefile" => Some('\u{f489}'),  // nf-oct-terminal
+        "dockerfile" => Some('\u{f308}'),                // nf-linux-docker
+        _ => None,
+    }
+}
+
+fn icon_for_code(lang: &str) -> char {
+    match lang {
+        "Rust" => '\u{e7a8}', // nf-dev-rust
+        "Python" => '\u{e73c}', // nf-dev-python
+        "JavaScript" | "TypeScript" | "TSX" | "JSX" | "TypeScriptReact" | "JavaScriptReact" => {
+            '\u{e74e}' // nf-dev-javascript
+        }
+        _ => '\u{f15c}', // nf-fa-file_text: plain page for everything else
+    }
+}
+
+/// Best-effort guess at whether the current terminal has a Nerd
+/// Font-patched typeface installed. Unlike color depth or sixel support,
+/// there's no escape sequence to ask a terminal "do you have glyph
+/// U+E7A8" — so this leans on the same `$NERD_FONT` opt-in signal that
+/// nerd-font-aware prompts (starship, oh-my-posh) already ask users to
+/// set, defaulting to `false` so unpatched terminals never see tofu boxes.
+pub fn supports_icons() -> bool {
+    if !std::io::stdout().is_terminal() {
+        return false;
+    }
+    matches!(std::env::var("NERD_FONT").as_deref(), Ok("1") | Ok("true"))
+}
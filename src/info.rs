@@ -3,6 +3,7 @@ use std::time::SystemTime;
 use unicode_width::UnicodeWidthStr;
 
 use crate::detect::FileFormat;
+use crate::icons;
 use crate::output::Output;
 use crate::theme::Theme;
 
@@ -10,6 +11,7 @@ pub fn print_header(
     path: Option<&Path>,
     format: Option<&FileFormat>,
     content: Option<&str>,
+    show_icon: bool,
     theme: &Theme,
     out: &Output,
 ) {
@@ -61,8 +63,18 @@ pub fn print_header(
         }
     }
 
+    // An icon needs both the flag/capability gate and an actual format and
+    // path to look up — stdin has neither a path nor always a format.
+    let icon = match (show_icon, path, format) {
+        (true, Some(p), Some(fmt)) => Some(icons::icon_for(fmt, p)),
+        _ => None,
+    };
+
     let sep = " \u{2502} ";
     let mut display_width: usize = 2; // "─ "
+    if icon.is_some() {
+        display_width += 2; // icon + trailing space
+    }
     display_width += UnicodeWidthStr::width(filename);
     let joined = segments.join(sep);
     if !segments.is_empty() {
@@ -75,13 +87,16 @@ pub fn print_header(
     let fill: String = "\u{2500}".repeat(fill_count);
 
     out.colored("\u{2500} ", theme.hr);
+    if let Some(glyph) = icon {
+        out.colored(&format!("{} ", glyph), theme.file_header);
+    }
     out.bold_colored(filename, theme.file_header);
     if !segments.is_empty() {
         out.colored(" \u{2502} ", theme.hr);
         out.dim(&joined, theme.line_number);
     }
     out.colored(&format!(" {}", fill), theme.hr);
-    println!();
+    out.newline();
 }
 
 fn format_language(format: &FileFormat) -> &str {
@@ -89,8 +104,7 @@ fn format_language(format: &FileFormat) -> &str {
         FileFormat::Markdown => "Markdown",
         FileFormat::Json => "JSON",
         FileFormat::Csv => "CSV",
-        FileFormat::Toml => "TOML",
-        FileFormat::Yaml => "YAML",
+        FileFormat::Org => "Org",
         FileFormat::Code(lang) => lang.as_str(),
         FileFormat::Image => "Image",
         FileFormat::Plain => "Plain Text",
@@ -0,0 +1,145 @@
+//! User-extensible extension/filename → language registry, loaded once from
+//! a `languages.toml` in the vita config dir (see `config::config_dir`) and
+//! consulted by `detect::detect_format`/`detect::detect_from_content` ahead
+//! of their built-in tables — user entries win. Mirrors the shape of the
+//! CodeMirror/Prism language tables: a name, the extensions/filenames that
+//! select it, and an optional syntect syntax to highlight it with.
+//!
+//! ```toml
+//! [[language]]
+//! name = "Astro"
+//! extensions = ["astro"]
+//! filenames = ["astro.config.mjs"]
+//! syntax = "HTML"   # extends detect::syntax_fallback for this language
+//! ```
+//!
+//! Parsing is deliberately loose, in the same spirit as `config::load` and
+//! `langprofile::load_overrides`: a missing or malformed `languages.toml`
+//! is just treated as "no user languages" rather than an error.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Default)]
+pub struct LanguageMap {
+    by_ext: HashMap<String, String>,
+    by_filename: HashMap<String, String>,
+    syntax_overrides: HashMap<String, String>,
+}
+
+impl LanguageMap {
+    pub fn lookup_ext(&self, ext: &str) -> Option<String> {
+        self.by_ext.get(ext).cloned()
+    }
+
+    pub fn lookup_filename(&self, name: &str) -> Option<String> {
+        self.by_filename.get(name).cloned()
+    }
+
+    /// Matches a shebang line against configured bare filenames, e.g. a
+    /// `roc` entry matching `#!/usr/bin/env roc`, the same way
+    /// `detect_from_content`'s existing shebang checks match interpreters.
+    pub fn lookup_shebang(&self, first_line: &str) -> Option<String> {
+        self.by_filename
+            .iter()
+            .find(|(name, _)| first_line.contains(name.as_str()))
+            .map(|(_, lang)| lang.clone())
+    }
+
+    pub fn syntax_override(&self, lang: &str) -> Option<&str> {
+        self.syntax_overrides.get(lang).map(|s| s.as_str())
+    }
+}
+
+/// The merged user + built-in language map, loaded on first use and cached
+/// for the life of the process.
+pub fn merged() -> &'static LanguageMap {
+    static CELL: OnceLock<LanguageMap> = OnceLock::new();
+    CELL.get_or_init(load)
+}
+
+fn load() -> LanguageMap {
+    let Some(dir) = crate::config::config_dir() else {
+        return LanguageMap::default();
+    };
+    let Ok(text) = std::fs::read_to_string(dir.join("languages.toml")) else {
+        return LanguageMap::default();
+    };
+    parse(&text)
+}
+
+fn parse(text: &str) -> LanguageMap {
+    let mut map = LanguageMap::default();
+
+    let Ok(doc) = toml::from_str::<toml::Value>(text) else {
+        return map;
+    };
+    let Some(entries) = doc.get("language").and_then(|v| v.as_array()) else {
+        return map;
+    };
+
+    for entry in entries {
+        let Some(name) = entry.get("name").and_then(|v| v.as_str()) else {
+            continue;
+        };
+
+        if let Some(exts) = entry.get("extensions").and_then(|v| v.as_array()) {
+            for ext in exts.iter().filter_map(|v| v.as_str()) {
+                map.by_ext.insert(ext.to_lowercase(), name.to_string());
+            }
+        }
+
+        if let Some(files) = entry.get("filenames").and_then(|v| v.as_array()) {
+            for file in files.iter().filter_map(|v| v.as_str()) {
+                map.by_filename.insert(file.to_lowercase(), name.to_string());
+            }
+        }
+
+        if let Some(syntax) = entry.get("syntax").and_then(|v| v.as_str()) {
+            map.syntax_overrides.insert(name.to_string(), syntax.to_string());
+        }
+    }
+
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_extension_and_filename() {
+        let map = parse(
+            r#"
+            [[language]]
+            name = "Astro"
+            extensions = ["astro"]
+            filenames = ["astro.config.mjs"]
+            syntax = "HTML"
+            "#,
+        );
+        assert_eq!(map.lookup_ext("astro"), Some("Astro".to_string()));
+        assert_eq!(map.lookup_filename("astro.config.mjs"), Some("Astro".to_string()));
+        assert_eq!(map.syntax_override("Astro"), Some("HTML"));
+        assert_eq!(map.lookup_ext("unknown"), None);
+    }
+
+    #[test]
+    fn test_parse_malformed_is_empty() {
+        let map = parse("not valid toml {{{");
+        assert_eq!(map.lookup_ext("astro"), None);
+    }
+
+    #[test]
+    fn test_lookup_shebang() {
+        let map = parse(
+            r#"
+            [[language]]
+            name = "Roc"
+            filenames = ["roc"]
+            "#,
+        );
+        assert_eq!(map.lookup_shebang("#!/usr/bin/env roc"), Some("Roc".to_string()));
+        assert_eq!(map.lookup_shebang("#!/usr/bin/env python3"), None);
+    }
+}
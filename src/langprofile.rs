@@ -0,0 +1,517 @@
+//! Pluggable per-language structural-definition registry
+//!
+//! Generalizes the keyword-prefix/heuristic pairs a `LanguageProfile` per
+//! language: its definition-keyword prefixes, its comment markers, and
+//! which `Matcher` decides whether a line is structural.
+//! `render::brief::is_structural_code_line` (its `--brief --grep`/JSON
+//! path) and its other heuristic functions (`is_c_func_def`,
+//! `is_asm_structural`, ...) are now thin wrappers around
+//! `is_structural_line`/this module's `pub(crate)` matcher functions.
+//! `render::brief`'s flat code outline and brace-scope tree still match
+//! against brief's own `keywords_for` table, not this one. `detect_definitions`
+//! is the whole-file entry point other crates/tools can call without
+//! reaching into `render::brief` internals at all.
+//!
+//! Built-in profiles can be overridden, or new ones added, by dropping a
+//! `.vita-languages.toml` next to where vita is invoked — see
+//! `load_overrides` for the schema.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Debug, Clone)]
+pub enum Matcher {
+    /// Line starts with one of the profile's keyword prefixes.
+    Prefix,
+    /// `int main(args) {` but not `if (`, `while (`, etc.
+    CFuncDef,
+    /// Top-level `name :: Type -> Type` signatures.
+    HaskellSig,
+    /// `name() {` / `name(){`.
+    ShellFunc,
+    /// CSS/SCSS selectors and at-rules ending in `{`.
+    CssSelector,
+    /// Batch file labels: `:name` but not `::comment`.
+    BatchLabel,
+    /// Assembly labels (`name:`) and `section`/`global` directives.
+    AsmLabel,
+    /// YAML top-level/second-level `key:` lines (indent of 2 or less).
+    YamlTopLevel,
+    /// TOML `[section]`/`[[array.of.tables]]` headers.
+    TomlSection,
+    /// HTML/template heading tags (`<title>`, `<h1>`..`<h6>`).
+    HtmlHeading,
+}
+
+#[derive(Debug, Clone)]
+pub struct LanguageProfile {
+    pub name: String,
+    pub keywords: Vec<String>,
+    pub comment_markers: Vec<String>,
+    pub matcher: Matcher,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Definition {
+    pub line: usize,
+    pub text: String,
+}
+
+/// Detects the language for `path` and returns every structural definition
+/// `source` contains, per that language's `LanguageProfile`.
+pub fn detect_definitions(path: &Path, source: &str) -> Vec<Definition> {
+    let profile = profile_for_path(path);
+    scan(source, &profile)
+}
+
+/// Single-line structural-definition check for `lang` — what
+/// `render::brief` calls per displayed line rather than scanning a whole
+/// file at once.
+pub fn is_structural_line(line: &str, lang: &str) -> bool {
+    let profile = profile_for(lang);
+    let keywords: Vec<&str> = profile
+        .keywords
+        .iter()
+        .filter(|k| !k.starts_with("ext:"))
+        .map(|s| s.as_str())
+        .collect();
+    is_structural(line, &keywords, &profile.matcher)
+}
+
+/// Resolves a `LanguageProfile` for `path`: a user override keyed by file
+/// extension takes priority, then the format vita already detects, then a
+/// profile looked up (and possibly overridden) by language name.
+pub fn profile_for_path(path: &Path) -> LanguageProfile {
+    let overrides = load_overrides();
+
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    if let Some(p) = overrides.values().find(|p| p.keywords_match_ext(&ext)) {
+        return p.clone();
+    }
+
+    let lang = match crate::detect::detect_format(path) {
+        crate::detect::FileFormat::Code(l) => l.to_lowercase(),
+        crate::detect::FileFormat::Markdown => "markdown".to_string(),
+        _ => "plain".to_string(),
+    };
+    profile_for(&lang)
+}
+
+/// Looks up a profile by normalized language name, preferring a user
+/// override over the built-in table, falling back to an empty profile
+/// (no keywords, no matcher hits) for languages nothing recognizes.
+pub fn profile_for(lang: &str) -> LanguageProfile {
+    let overrides = load_overrides();
+    if let Some(p) = overrides.get(lang) {
+        return p.clone();
+    }
+    builtin_profiles()
+        .into_iter()
+        .find(|p| p.name == lang)
+        .unwrap_or_else(|| LanguageProfile {
+            name: lang.to_string(),
+            keywords: Vec::new(),
+            comment_markers: Vec::new(),
+            matcher: Matcher::Prefix,
+        })
+}
+
+impl LanguageProfile {
+    /// Used only by override profiles, which carry their own extension list
+    /// packed into `keywords` under an `ext:` marker (see `load_overrides`).
+    fn keywords_match_ext(&self, ext: &str) -> bool {
+        self.keywords
+            .iter()
+            .any(|k| k.strip_prefix("ext:").map(|e| e == ext).unwrap_or(false))
+    }
+}
+
+fn scan(source: &str, profile: &LanguageProfile) -> Vec<Definition> {
+    let keywords: Vec<&str> = profile
+        .keywords
+        .iter()
+        .filter(|k| !k.starts_with("ext:"))
+        .map(|s| s.as_str())
+        .collect();
+
+    source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| is_structural(line, &keywords, &profile.matcher))
+        .map(|(i, line)| Definition {
+            line: i + 1,
+            text: line.to_string(),
+        })
+        .collect()
+}
+
+fn is_structural(line: &str, keywords: &[&str], matcher: &Matcher) -> bool {
+    let trimmed = line.trim_start();
+
+    if matches_keyword(trimmed, keywords) {
+        return true;
+    }
+
+    match matcher {
+        Matcher::Prefix => false,
+        Matcher::CFuncDef => is_c_func_def(line),
+        Matcher::HaskellSig => has_haskell_sig(line),
+        Matcher::ShellFunc => is_shell_func(line),
+        Matcher::CssSelector => is_css_selector(trimmed),
+        Matcher::BatchLabel => trimmed.starts_with(':') && !trimmed.starts_with("::"),
+        Matcher::AsmLabel => is_asm_label(trimmed),
+        Matcher::YamlTopLevel => {
+            !line.is_empty() && line.len() - line.trim_start().len() <= 2 && line.contains(':')
+        }
+        Matcher::TomlSection => trimmed.starts_with('['),
+        Matcher::HtmlHeading => is_html_heading(trimmed),
+    }
+}
+
+pub(crate) fn matches_keyword(trimmed: &str, keywords: &[&str]) -> bool {
+    keywords.iter().any(|kw| trimmed.starts_with(kw))
+}
+
+pub(crate) fn is_c_func_def(line: &str) -> bool {
+    if line.starts_with(' ') || line.starts_with('\t') {
+        return false;
+    }
+    if !line.contains('(') {
+        return false;
+    }
+    let control = [
+        "if ", "if(", "else ", "while ", "while(", "for ", "for(", "switch ", "switch(",
+        "return ", "return(", "//", "/*", "#",
+    ];
+    if control.iter().any(|kw| line.starts_with(kw)) {
+        return false;
+    }
+    let paren_pos = line.find('(').unwrap();
+    let before = line[..paren_pos].trim();
+    before.contains(' ') || before.contains('*')
+}
+
+pub(crate) fn has_haskell_sig(line: &str) -> bool {
+    if line.starts_with(' ') || line.starts_with('\t') || line.starts_with("--") {
+        return false;
+    }
+    line.contains(" :: ")
+}
+
+pub(crate) fn is_shell_func(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.ends_with("() {") || trimmed.ends_with("(){")
+}
+
+pub(crate) fn is_css_selector(trimmed: &str) -> bool {
+    let t = trimmed.trim();
+    if t.is_empty() || t.starts_with("/*") || t.starts_with("//") {
+        return false;
+    }
+    if t.starts_with('@') {
+        return true;
+    }
+    t.ends_with('{')
+}
+
+fn is_html_heading(trimmed: &str) -> bool {
+    let lower = trimmed.to_lowercase();
+    ["<title", "<h1", "<h2", "<h3", "<h4", "<h5", "<h6"]
+        .iter()
+        .any(|tag| lower.starts_with(tag))
+}
+
+pub(crate) fn is_asm_label(trimmed: &str) -> bool {
+    let t = trimmed.trim();
+    if t.is_empty() || t.starts_with(';') {
+        return false;
+    }
+    if t.contains(':') && !t.starts_with('.') {
+        let colon_pos = t.find(':').unwrap();
+        let before = &t[..colon_pos];
+        if !before.is_empty() && before.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return true;
+        }
+    }
+    let lower = t.to_lowercase();
+    lower.starts_with("section ") || lower.starts_with("global ") || lower.starts_with(".section ")
+}
+
+/// Every alias this function registers shares one profile body — `render::brief`
+/// has long recognized several names (`kt`/`kotlin`, `bash`/`sh`/`zsh`/...)
+/// as the same language, and those aliases are preserved here rather than
+/// dropped, so switching `render::brief` over to this table doesn't lose
+/// coverage for any language it already handled.
+fn builtin_profiles() -> Vec<LanguageProfile> {
+    fn p(name: &str, keywords: &[&str], comments: &[&str], matcher: Matcher) -> LanguageProfile {
+        LanguageProfile {
+            name: name.to_string(),
+            keywords: keywords.iter().map(|s| s.to_string()).collect(),
+            comment_markers: comments.iter().map(|s| s.to_string()).collect(),
+            matcher,
+        }
+    }
+
+    fn pm(names: &[&str], keywords: &[&str], comments: &[&str], matcher: Matcher) -> Vec<LanguageProfile> {
+        names.iter().map(|name| p(name, keywords, comments, matcher.clone())).collect()
+    }
+
+    let mut profiles = Vec::new();
+
+    profiles.push(p(
+        "rust",
+        &[
+            "fn ", "pub fn ", "pub(crate) fn ", "pub(super) fn ", "struct ", "pub struct ",
+            "pub(crate) struct ", "enum ", "pub enum ", "pub(crate) enum ", "trait ",
+            "pub trait ", "impl ", "mod ", "pub mod ", "pub(crate) mod ",
+        ],
+        &["//", "/*"],
+        Matcher::Prefix,
+    ));
+    profiles.push(p(
+        "python",
+        &["import ", "from ", "class ", "def ", "async def ", "if __name__"],
+        &["#"],
+        Matcher::Prefix,
+    ));
+    profiles.extend(pm(
+        &["javascript", "jsx", "javascriptreact"],
+        &["import ", "export ", "function ", "async function ", "class ", "const ", "let "],
+        &["//", "/*"],
+        Matcher::Prefix,
+    ));
+    profiles.extend(pm(
+        &["typescript", "tsx", "typescriptreact"],
+        &[
+            "import ", "export ", "function ", "async function ", "class ", "const ",
+            "let ", "interface ", "type ", "enum ",
+        ],
+        &["//", "/*"],
+        Matcher::Prefix,
+    ));
+    profiles.push(p("go", &["package ", "import ", "type ", "func "], &["//", "/*"], Matcher::Prefix));
+    profiles.push(p(
+        "java",
+        &["package ", "import ", "class ", "interface ", "enum ", "public ", "private ", "protected "],
+        &["//", "/*"],
+        Matcher::Prefix,
+    ));
+    profiles.push(p("c", &["#include ", "typedef ", "struct ", "union ", "enum "], &["//", "/*"], Matcher::CFuncDef));
+    profiles.extend(pm(
+        &["c++", "objective-c", "objective-c++"],
+        &["#include ", "typedef ", "struct ", "union ", "enum ", "class ", "namespace ", "template "],
+        &["//", "/*"],
+        Matcher::CFuncDef,
+    ));
+    profiles.push(p(
+        "c#",
+        &["using ", "namespace ", "class ", "interface ", "struct ", "enum ", "public ", "private ", "protected ", "internal "],
+        &["//", "/*"],
+        Matcher::Prefix,
+    ));
+    profiles.push(p("ruby", &["require ", "require_relative ", "module ", "class ", "def "], &["#"], Matcher::Prefix));
+    profiles.push(p(
+        "php",
+        &["namespace ", "use ", "class ", "function ", "public function ", "private function ", "protected function "],
+        &["//", "#", "/*"],
+        Matcher::Prefix,
+    ));
+    profiles.extend(pm(
+        &["kotlin", "kt"],
+        &["package ", "import ", "class ", "data class ", "sealed class ", "object ", "fun "],
+        &["//", "/*"],
+        Matcher::Prefix,
+    ));
+    profiles.push(p(
+        "swift",
+        &["import ", "protocol ", "struct ", "class ", "func ", "enum ", "extension "],
+        &["//", "/*"],
+        Matcher::Prefix,
+    ));
+    profiles.push(p("lua", &["function ", "local function "], &["--"], Matcher::Prefix));
+    profiles.extend(pm(
+        &["scala", "sbt"],
+        &["package ", "import ", "trait ", "class ", "case class ", "object ", "def "],
+        &["//", "/*"],
+        Matcher::Prefix,
+    ));
+    profiles.push(p("zig", &["const ", "pub const ", "fn ", "pub fn "], &["//"], Matcher::Prefix));
+    profiles.extend(pm(
+        &["elixir", "ex"],
+        &["defmodule ", "def ", "defp "],
+        &["#"],
+        Matcher::Prefix,
+    ));
+    profiles.extend(pm(
+        &["haskell", "hs"],
+        &["module ", "import ", "data ", "type ", "class "],
+        &["--", "{-"],
+        Matcher::HaskellSig,
+    ));
+    profiles.extend(pm(
+        &["sql", "ddl", "dml"],
+        &["CREATE ", "ALTER ", "DROP ", "create ", "alter ", "drop "],
+        &["--", "/*"],
+        Matcher::Prefix,
+    ));
+    profiles.extend(pm(
+        &["bash", "sh", "zsh", "fish", "shell", "bourne again shell (bash)"],
+        &["#!", "source ", "function "],
+        &["#"],
+        Matcher::ShellFunc,
+    ));
+    profiles.extend(pm(
+        &["powershell", "ps1"],
+        &["#!", "source ", "function "],
+        &["#"],
+        Matcher::Prefix,
+    ));
+    profiles.push(p("r", &["library(", "require(", "source("], &["#"], Matcher::Prefix));
+    profiles.push(p("perl", &["use ", "package ", "sub "], &["#"], Matcher::Prefix));
+    profiles.push(p(
+        "d",
+        &["import ", "module ", "class ", "struct ", "interface ", "void ", "auto "],
+        &["//", "/*"],
+        Matcher::Prefix,
+    ));
+    profiles.extend(pm(
+        &["ocaml", "ml"],
+        &["let ", "module ", "type ", "val ", "open "],
+        &["(*"],
+        Matcher::Prefix,
+    ));
+    profiles.push(p("clojure", &["(ns ", "(def ", "(defn ", "(defmacro "], &[";"], Matcher::Prefix));
+    profiles.push(p("erlang", &["-module(", "-export(", "-import("], &["%"], Matcher::Prefix));
+    profiles.extend(pm(
+        &["lisp", "scheme"],
+        &["(define ", "(defun ", "(defmacro ", "(defvar "],
+        &[";"],
+        Matcher::Prefix,
+    ));
+    profiles.extend(pm(
+        &["groovy", "gradle"],
+        &["package ", "import ", "class ", "interface ", "def "],
+        &["//", "/*"],
+        Matcher::Prefix,
+    ));
+    profiles.extend(pm(
+        &["pascal", "delphi"],
+        &["program ", "unit ", "uses ", "type ", "procedure ", "function "],
+        &["//", "{"],
+        Matcher::Prefix,
+    ));
+    profiles.push(p("makefile", &[".PHONY", "define "], &["#"], Matcher::Prefix));
+    profiles.push(p(
+        "dockerfile",
+        &["FROM ", "RUN ", "CMD ", "ENTRYPOINT ", "COPY ", "ADD ", "ENV ", "EXPOSE "],
+        &["#"],
+        Matcher::Prefix,
+    ));
+    profiles.extend(pm(
+        &["terraform", "tf", "hcl"],
+        &["resource ", "data ", "variable ", "output ", "module ", "provider "],
+        &["#"],
+        Matcher::Prefix,
+    ));
+    profiles.extend(pm(
+        &["graphql", "gql"],
+        &["type ", "input ", "enum ", "interface ", "query ", "mutation ", "subscription "],
+        &["#"],
+        Matcher::Prefix,
+    ));
+    profiles.extend(pm(
+        &["protocol buffers", "proto"],
+        &["syntax ", "package ", "message ", "service ", "enum ", "rpc "],
+        &["//"],
+        Matcher::Prefix,
+    ));
+    profiles.extend(pm(&["css", "scss", "sass", "less"], &[], &["/*"], Matcher::CssSelector));
+    profiles.extend(pm(&["batch file", "bat", "cmd"], &[], &["::"], Matcher::BatchLabel));
+    profiles.extend(pm(&["asm", "nasm", "assembly"], &[], &[";"], Matcher::AsmLabel));
+    profiles.extend(pm(&["yaml", "yml"], &[], &["#"], Matcher::YamlTopLevel));
+    profiles.push(p("toml", &[], &["#"], Matcher::TomlSection));
+    profiles.extend(pm(&["html", "html (rails)", "html (tcl)"], &[], &["<!--"], Matcher::HtmlHeading));
+
+    profiles
+}
+
+/// Loads `.vita-languages.toml` from the current directory, if present.
+/// Each `[[language]]` table becomes one profile keyed by `name`:
+///
+/// ```toml
+/// [[language]]
+/// name = "zig"
+/// extensions = ["zig"]
+/// keywords = ["fn ", "pub fn ", "const "]
+/// comment_markers = ["//"]
+/// matcher = "prefix"   # prefix | c_func_def | haskell_sig | shell_func | css_selector | batch_label | asm_label | yaml_top_level | toml_section | html_heading
+/// ```
+///
+/// `extensions` entries are stashed in `keywords` as `ext:<name>` so
+/// `profile_for_path` can match on them without a second lookup table.
+fn load_overrides() -> HashMap<String, LanguageProfile> {
+    let mut profiles = HashMap::new();
+
+    let Ok(text) = std::fs::read_to_string(".vita-languages.toml") else {
+        return profiles;
+    };
+    let Ok(doc) = toml::from_str::<toml::Value>(&text) else {
+        return profiles;
+    };
+    let Some(entries) = doc.get("language").and_then(|v| v.as_array()) else {
+        return profiles;
+    };
+
+    for entry in entries {
+        let Some(name) = entry.get("name").and_then(|v| v.as_str()) else {
+            continue;
+        };
+
+        let mut keywords: Vec<String> = entry
+            .get("keywords")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str()).map(String::from).collect())
+            .unwrap_or_default();
+
+        if let Some(exts) = entry.get("extensions").and_then(|v| v.as_array()) {
+            for ext in exts.iter().filter_map(|v| v.as_str()) {
+                keywords.push(format!("ext:{}", ext.to_lowercase()));
+            }
+        }
+
+        let comment_markers = entry
+            .get("comment_markers")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str()).map(String::from).collect())
+            .unwrap_or_default();
+
+        let matcher = match entry.get("matcher").and_then(|v| v.as_str()) {
+            Some("c_func_def") => Matcher::CFuncDef,
+            Some("haskell_sig") => Matcher::HaskellSig,
+            Some("shell_func") => Matcher::ShellFunc,
+            Some("css_selector") => Matcher::CssSelector,
+            Some("batch_label") => Matcher::BatchLabel,
+            Some("asm_label") => Matcher::AsmLabel,
+            Some("yaml_top_level") => Matcher::YamlTopLevel,
+            Some("toml_section") => Matcher::TomlSection,
+            Some("html_heading") => Matcher::HtmlHeading,
+            _ => Matcher::Prefix,
+        };
+
+        profiles.insert(
+            name.to_lowercase(),
+            LanguageProfile {
+                name: name.to_lowercase(),
+                keywords,
+                comment_markers,
+                matcher,
+            },
+        );
+    }
+
+    profiles
+}
@@ -3,10 +3,20 @@ use std::io::{self, IsTerminal, Read};
 use std::path::PathBuf;
 use std::process;
 
+mod classifier;
+mod config;
 mod detect;
+mod heuristics;
+mod icons;
 mod info;
+mod langmap;
+mod langprofile;
+mod modeline;
+mod modwalk;
 mod output;
 mod render;
+mod stats;
+mod symbols;
 mod theme;
 
 use detect::{detect_format, FileFormat};
@@ -30,18 +40,94 @@ struct Cli {
     #[arg(short = 'b', long = "brief")]
     brief: bool,
 
+    /// Output format for --brief or --stats: "text" (default) or "json"
+    #[arg(long = "format", default_value = "text", value_name = "FORMAT")]
+    format: String,
+
+    /// With --brief on a Rust file, also follow its `mod foo;` declarations
+    /// and outline every file reached
+    #[arg(short = 'm', long = "follow-mods")]
+    follow_mods: bool,
+
     /// Git blame: show who changed each line
     #[arg(short = 'B', long = "blame")]
     blame: bool,
 
-    /// Grep: show only lines matching PAT with highlight
+    /// Git diff: show working tree changes vs HEAD, with word-level highlighting
+    #[arg(short = 'd', long = "diff")]
+    diff: bool,
+
+    /// Show exactly two files (or a file and stdin "-") in aligned side-by-side columns
+    #[arg(short = 'y', long = "side-by-side")]
+    side_by_side: bool,
+
+    /// Show file(s) as a hex/ASCII dump instead of rendering as text
+    #[arg(long = "hex")]
+    hex: bool,
+
+    /// Hex/binary diff: compare the input against FILE byte-for-byte,
+    /// cmp/xxd-diff style, instead of rendering as text
+    #[arg(long = "hex-diff", value_name = "FILE")]
+    hex_diff: Option<PathBuf>,
+
+    /// Show only the rows of a --hex view that contain PAT (plus --context N
+    /// rows around each hit), with the matches highlighted; rows without a
+    /// match are not shown. PAT is parsed as a hex
+    /// byte pattern (e.g. "de ad be ef") if every token is a two-digit hex
+    /// byte, otherwise matched as literal text
+    #[arg(long = "hex-grep", value_name = "PAT")]
+    hex_grep: Option<String>,
+
+    /// Scan for Trojan Source bidi/zero-width characters, reporting only
+    /// files that contain them
+    #[arg(long = "audit")]
+    audit: bool,
+
+    /// Tally code/comment/blank lines per language across all inputs and
+    /// print a summary table (or JSON with --format=json)
+    #[arg(long = "stats")]
+    stats: bool,
+
+    /// Grep: show only lines matching the regex PAT, with matches highlighted
     #[arg(short = 'g', long = "grep", value_name = "PAT")]
     grep: Option<String>,
 
+    /// Show N lines of context after each match (--grep only)
+    #[arg(short = 'A', long = "after", value_name = "N")]
+    after: Option<usize>,
+
+    /// Show N lines of context before each match (--grep only)
+    #[arg(long = "before", value_name = "N")]
+    before: Option<usize>,
+
+    /// Show N lines of context before and after each match (--grep only)
+    #[arg(short = 'C', long = "context", value_name = "N")]
+    context: Option<usize>,
+
+    /// Force case-sensitive matching for --grep, overriding smart-case
+    #[arg(short = 's', long = "case-sensitive")]
+    case_sensitive: bool,
+
+    /// Smart-case matching for --grep (default): case-insensitive unless
+    /// PAT contains an uppercase letter
+    #[arg(short = 'S', long = "smart-case")]
+    smart_case: bool,
+
     /// Show file info header
     #[arg(short = 'i', long = "info")]
     info: bool,
 
+    /// Show a Nerd Font file-type icon before the filename in the --info
+    /// header. Falls back to the user config file, then off. No effect
+    /// without a Nerd Font-patched terminal (see $NERD_FONT).
+    #[arg(long = "icons")]
+    icons: bool,
+
+    /// Append a table of contents after a rendered Markdown document, with
+    /// hyperlinked anchors for each heading
+    #[arg(long = "toc")]
+    toc: bool,
+
     /// Force language for syntax highlighting
     #[arg(short = 'l', long = "lang")]
     lang: Option<String>,
@@ -58,13 +144,23 @@ struct Cli {
     #[arg(short = 'r', long = "raw")]
     raw: bool,
 
-    /// Color theme (--list-themes to see all)
-    #[arg(short = 't', long = "theme", default_value = "dracula")]
-    theme: String,
+    /// Color theme (--list-themes to see all). Falls back to the user
+    /// config file, then "dracula".
+    #[arg(short = 't', long = "theme")]
+    theme: Option<String>,
 
-    /// Max image width in characters
-    #[arg(short = 'w', long = "width", default_value_t = 60)]
-    width: u32,
+    /// Max image width in characters. Falls back to the user config file,
+    /// then 60.
+    #[arg(short = 'w', long = "width")]
+    width: Option<u32>,
+
+    /// Cap animated GIF/WebP playback at N loops (default: loop forever)
+    #[arg(long = "frames", value_name = "N")]
+    frames: Option<usize>,
+
+    /// Show only the first frame of animated GIF/WebP, instead of looping
+    #[arg(long = "no-anim")]
+    no_anim: bool,
 
     /// Show only the first N lines
     #[arg(long = "head", value_name = "N")]
@@ -81,10 +177,32 @@ struct Cli {
     /// Print version
     #[arg(short = 'v', long = "version")]
     show_version: bool,
+
+    /// When to use a pager: "auto" (default, only when output exceeds one
+    /// screen), "always", or "never"
+    #[arg(long = "paging", default_value = "auto", value_name = "WHEN")]
+    paging: String,
+
+    /// Print the resolved user config directory and exit
+    #[arg(long = "config-dir")]
+    config_dir: bool,
+
+    /// Skip vita/config.toml. User files under vita/themes/ and
+    /// vita/syntaxes/ are still loaded
+    #[arg(long = "no-config")]
+    no_config: bool,
 }
 
 fn main() {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+
+    if cli.config_dir {
+        match config::config_dir() {
+            Some(dir) => println!("{}", dir.display()),
+            None => eprintln!("vita: could not determine a config directory for this platform"),
+        }
+        return;
+    }
 
     if cli.show_version {
         println!("vita {}", env!("CARGO_PKG_VERSION"));
@@ -96,6 +214,16 @@ fn main() {
         return;
     }
 
+    let cfg = if cli.no_config { config::Config::default() } else { config::load() };
+    let theme_name = cli
+        .theme
+        .clone()
+        .or_else(|| cfg.theme.clone())
+        .unwrap_or_else(|| "dracula".to_string());
+    let width = cli.width.or(cfg.width).unwrap_or(60);
+    cli.line_numbers = cli.line_numbers || cfg.line_numbers.unwrap_or(false);
+    cli.icons = (cli.icons || cfg.icons.unwrap_or(false)) && icons::supports_icons();
+
     if cli.head.is_some() && cli.tail.is_some() {
         eprintln!("vita: --head and --tail cannot be used together");
         process::exit(1);
@@ -116,16 +244,114 @@ fn main() {
         process::exit(1);
     }
 
-    let theme = match Theme::from_name(&cli.theme) {
+    if cli.diff && (cli.brief || cli.show_all || cli.grep.is_some() || cli.blame) {
+        eprintln!("vita: --diff cannot be combined with --brief, --show-all, --grep, or --blame");
+        process::exit(1);
+    }
+
+    if cli.side_by_side && (cli.brief || cli.show_all || cli.grep.is_some() || cli.blame || cli.diff) {
+        eprintln!(
+            "vita: --side-by-side cannot be combined with --brief, --show-all, --grep, --blame, or --diff"
+        );
+        process::exit(1);
+    }
+
+    if cli.side_by_side && cli.files.len() != 2 {
+        eprintln!("vita: --side-by-side requires exactly two files (or a file and stdin '-')");
+        process::exit(1);
+    }
+
+    if cli.audit && (cli.brief || cli.show_all || cli.grep.is_some() || cli.blame || cli.diff || cli.side_by_side) {
+        eprintln!(
+            "vita: --audit cannot be combined with --brief, --show-all, --grep, --blame, --diff, or --side-by-side"
+        );
+        process::exit(1);
+    }
+
+    if cli.stats
+        && (cli.brief || cli.show_all || cli.grep.is_some() || cli.blame || cli.diff || cli.side_by_side || cli.audit)
+    {
+        eprintln!(
+            "vita: --stats cannot be combined with --brief, --show-all, --grep, --blame, --diff, --side-by-side, or --audit"
+        );
+        process::exit(1);
+    }
+
+    if cli.hex && cli.hex_diff.is_some() {
+        eprintln!("vita: --hex is implied by --hex-diff; use one or the other");
+        process::exit(1);
+    }
+
+    if (cli.hex || cli.hex_diff.is_some())
+        && (cli.brief
+            || cli.show_all
+            || cli.grep.is_some()
+            || cli.blame
+            || cli.diff
+            || cli.side_by_side
+            || cli.audit
+            || cli.stats)
+    {
+        eprintln!(
+            "vita: --hex/--hex-diff cannot be combined with --brief, --show-all, --grep, --blame, --diff, --side-by-side, --audit, or --stats"
+        );
+        process::exit(1);
+    }
+
+    if cli.hex_grep.is_some() && !cli.hex {
+        eprintln!("vita: --hex-grep requires --hex (it has no effect on --hex-diff)");
+        process::exit(1);
+    }
+
+    if cli.hex_diff.is_some() && cli.files.len() > 1 {
+        eprintln!("vita: --hex-diff takes a single input file (or stdin '-'); pass the file to compare against as its argument");
+        process::exit(1);
+    }
+
+    if cli.follow_mods && !cli.brief {
+        eprintln!("vita: --follow-mods requires --brief");
+        process::exit(1);
+    }
+
+    if cli.case_sensitive && cli.smart_case {
+        eprintln!("vita: --case-sensitive and --smart-case cannot be used together");
+        process::exit(1);
+    }
+
+    if (cli.after.is_some() || cli.before.is_some() || cli.context.is_some())
+        && cli.grep.is_none()
+        && cli.hex_grep.is_none()
+    {
+        eprintln!("vita: --after/--before/--context require --grep or --hex-grep");
+        process::exit(1);
+    }
+
+    if cli.hex_grep.is_some() && (cli.after.is_some() || cli.before.is_some()) {
+        eprintln!("vita: --hex-grep only supports --context, not --after/--before");
+        process::exit(1);
+    }
+
+    let theme = match Theme::from_name(&theme_name) {
         Some(t) => t,
         None => {
-            eprintln!("vita: unknown theme '{}'", cli.theme);
+            eprintln!("vita: unknown theme '{}'", theme_name);
             eprintln!();
             Theme::list_all_to(&mut io::stderr());
             process::exit(1);
         }
     };
-    let out = Output::new(!cli.plain && io::stdout().is_terminal());
+    let paging = match output::Paging::parse(&cli.paging) {
+        Some(p) => p,
+        None => {
+            eprintln!("vita: unknown --paging value '{}' (expected auto, always, or never)", cli.paging);
+            process::exit(1);
+        }
+    };
+    // Plain/non-interactive output shouldn't be paged: `cli.plain` strips
+    // formatting for piping, and a non-tty stdout means there's no screen
+    // to overflow.
+    let paging = if cli.plain { output::Paging::Never } else { paging };
+    let out = Output::with_paging(!cli.plain && io::stdout().is_terminal(), paging);
 
     if cli.show_all {
         return run_show_all(&cli, &theme, &out);
@@ -135,6 +361,26 @@ fn main() {
         return run_blame(&cli, &theme, &out);
     }
 
+    if cli.diff {
+        return run_diff(&cli, &theme, &out);
+    }
+
+    if cli.side_by_side {
+        return run_side_by_side(&cli, &theme, &out);
+    }
+
+    if cli.hex || cli.hex_diff.is_some() {
+        return run_hex(&cli, &theme, &out);
+    }
+
+    if cli.audit {
+        return run_audit(&cli, &theme, &out);
+    }
+
+    if cli.stats {
+        return run_stats(&cli, &theme, &out);
+    }
+
     if cli.brief {
         if let Some(ref pattern) = cli.grep {
             return run_brief_grep(&cli, pattern, &theme, &out);
@@ -167,7 +413,7 @@ fn main() {
             .unwrap_or_else(|| detect::detect_from_content(&buf));
 
         if cli.info {
-            info::print_header(None, Some(&format), Some(&buf), &theme, &out);
+            info::print_header(None, Some(&format), Some(&buf), cli.icons, &theme, &out);
         }
         render_content(&buf, &format, &cli, &theme, &out);
         return;
@@ -182,7 +428,7 @@ fn main() {
                 let buf = truncate_lines(&buf, cli.head, cli.tail);
                 let format = detect::detect_from_content(&buf);
                 if cli.info {
-                    info::print_header(None, Some(&format), Some(&buf), &theme, &out);
+                    info::print_header(None, Some(&format), Some(&buf), cli.icons, &theme, &out);
                 }
                 render_content(&buf, &format, &cli, &theme, &out);
             }
@@ -207,15 +453,15 @@ fn main() {
         match &format {
             FileFormat::Image => {
                 if cli.info {
-                    info::print_header(Some(path), Some(&format), None, &theme, &out);
+                    info::print_header(Some(path), Some(&format), None, cli.icons, &theme, &out);
                 }
-                render::image::render(path, cli.width, &theme, &out);
+                render::image::render(path, width, cli.frames, cli.no_anim, &theme, &out);
             }
             _ => match std::fs::read_to_string(path) {
                 Ok(content) => {
                     let content = truncate_lines(&content, cli.head, cli.tail);
                     if cli.info {
-                        info::print_header(Some(path), Some(&format), Some(&content), &theme, &out);
+                        info::print_header(Some(path), Some(&format), Some(&content), cli.icons, &theme, &out);
                     }
                     render_content(&content, &format, &cli, &theme, &out);
                 }
@@ -247,7 +493,7 @@ fn run_show_all(cli: &Cli, theme: &Theme, out: &Output) {
                 .as_deref()
                 .map(|l| FileFormat::Code(l.to_string()))
                 .unwrap_or_else(|| detect::detect_from_content(&buf));
-            info::print_header(None, Some(&format), Some(&buf), theme, out);
+            info::print_header(None, Some(&format), Some(&buf), cli.icons, theme, out);
         }
         render::showall::render(&buf, theme, out);
         return;
@@ -262,7 +508,7 @@ fn run_show_all(cli: &Cli, theme: &Theme, out: &Output) {
                 let buf = truncate_lines(&buf, cli.head, cli.tail);
                 if cli.info {
                     let format = detect::detect_from_content(&buf);
-                    info::print_header(None, Some(&format), Some(&buf), theme, out);
+                    info::print_header(None, Some(&format), Some(&buf), cli.icons, theme, out);
                 }
                 render::showall::render(&buf, theme, out);
             }
@@ -287,7 +533,7 @@ fn run_show_all(cli: &Cli, theme: &Theme, out: &Output) {
                         .as_deref()
                         .map(|l| FileFormat::Code(l.to_string()))
                         .unwrap_or_else(|| detect_format(path));
-                    info::print_header(Some(path), Some(&format), Some(&content), theme, out);
+                    info::print_header(Some(path), Some(&format), Some(&content), cli.icons, theme, out);
                 }
                 render::showall::render(&content, theme, out);
             }
@@ -296,6 +542,111 @@ fn run_show_all(cli: &Cli, theme: &Theme, out: &Output) {
     }
 }
 
+fn run_audit(cli: &Cli, theme: &Theme, out: &Output) {
+    let scan = |name: &str, content: &str| {
+        let count = render::showall::count_suspicious(content);
+        if count > 0 {
+            out.colored(name, theme.file_header);
+            out.raw(": ");
+            out.colored(
+                &format!("{} suspicious bidi/zero-width character(s)", count),
+                theme.alert_caution,
+            );
+            out.newline();
+        }
+    };
+
+    if cli.files.is_empty() {
+        if io::stdin().is_terminal() {
+            eprintln!("vita: no input. Use 'vita --help' for usage.");
+            process::exit(1);
+        }
+
+        let mut buf = String::new();
+        if io::stdin().read_to_string(&mut buf).is_err() {
+            eprintln!("vita: failed to read stdin");
+            process::exit(1);
+        }
+        scan("stdin", &buf);
+        return;
+    }
+
+    for path in &cli.files {
+        if path.to_str() == Some("-") {
+            let mut buf = String::new();
+            if io::stdin().read_to_string(&mut buf).is_ok() {
+                scan("stdin", &buf);
+            }
+            continue;
+        }
+
+        if !path.exists() {
+            eprintln!("vita: '{}': No such file or directory", path.display());
+            continue;
+        }
+
+        match std::fs::read_to_string(path) {
+            Ok(content) => scan(&path.display().to_string(), &content),
+            Err(e) => eprintln!("vita: '{}': {}", path.display(), e),
+        }
+    }
+}
+
+fn run_stats(cli: &Cli, theme: &Theme, out: &Output) {
+    let mut collector = render::stats::Collector::new();
+
+    if cli.files.is_empty() {
+        if io::stdin().is_terminal() {
+            eprintln!("vita: no input. Use 'vita --help' for usage.");
+            process::exit(1);
+        }
+
+        let mut buf = String::new();
+        if io::stdin().read_to_string(&mut buf).is_err() {
+            eprintln!("vita: failed to read stdin");
+            process::exit(1);
+        }
+        let format = detect::detect_from_content(&buf);
+        collector.add(&format, &buf);
+    } else {
+        for path in &cli.files {
+            if path.to_str() == Some("-") {
+                let mut buf = String::new();
+                if io::stdin().read_to_string(&mut buf).is_ok() {
+                    let format = detect::detect_from_content(&buf);
+                    collector.add(&format, &buf);
+                }
+                continue;
+            }
+
+            if !path.exists() {
+                eprintln!("vita: '{}': No such file or directory", path.display());
+                continue;
+            }
+
+            let format = detect_format(path);
+            if matches!(format, FileFormat::Image) {
+                continue;
+            }
+
+            match std::fs::read_to_string(path) {
+                Ok(content) => collector.add(&format, &content),
+                Err(e) => eprintln!("vita: '{}': {}", path.display(), e),
+            }
+        }
+    }
+
+    if collector.is_empty() {
+        return;
+    }
+
+    if cli.format == "json" {
+        collector.render_json(out);
+    } else {
+        collector.render(theme, out);
+    }
+}
+
 fn run_blame(cli: &Cli, theme: &Theme, out: &Output) {
     if cli.files.is_empty() {
         eprintln!("vita: --blame requires a file argument");
@@ -333,14 +684,193 @@ fn run_blame(cli: &Cli, theme: &Theme, out: &Output) {
         };
 
         if cli.info {
-            info::print_header(Some(path), Some(&format), None, theme, out);
+            info::print_header(Some(path), Some(&format), None, cli.icons, theme, out);
         }
 
         render::blame::render(path, lang, cli.head, cli.tail, theme, out);
     }
 }
 
+fn run_diff(cli: &Cli, theme: &Theme, out: &Output) {
+    if cli.files.is_empty() {
+        eprintln!("vita: --diff requires a file argument");
+        process::exit(1);
+    }
+
+    let multi = cli.files.len() > 1;
+
+    for path in &cli.files {
+        if path.to_str() == Some("-") {
+            eprintln!("vita: --diff cannot read from stdin");
+            continue;
+        }
+
+        if !path.exists() {
+            eprintln!("vita: '{}': No such file or directory", path.display());
+            continue;
+        }
+
+        if multi {
+            out.file_separator(&path.display().to_string(), theme);
+        }
+
+        let format = cli
+            .lang
+            .as_deref()
+            .map(|l| FileFormat::Code(l.to_string()))
+            .unwrap_or_else(|| detect_format(path));
+
+        let lang = match &format {
+            FileFormat::Code(l) => l.as_str(),
+            FileFormat::Markdown => "Markdown",
+            FileFormat::Json => "JSON",
+            _ => "Plain Text",
+        };
+
+        if cli.info {
+            info::print_header(Some(path), Some(&format), None, cli.icons, theme, out);
+        }
+
+        render::diff::render(path, lang, theme, out);
+    }
+}
+
+fn run_side_by_side(cli: &Cli, theme: &Theme, out: &Output) {
+    let read_one = |path: &PathBuf| -> Option<(String, String)> {
+        if path.to_str() == Some("-") {
+            let mut buf = String::new();
+            if io::stdin().read_to_string(&mut buf).is_err() {
+                eprintln!("vita: failed to read stdin");
+                return None;
+            }
+            Some(("stdin".to_string(), buf))
+        } else if path.exists() {
+            match std::fs::read_to_string(path) {
+                Ok(content) => Some((path.display().to_string(), content)),
+                Err(e) => {
+                    eprintln!("vita: '{}': {}", path.display(), e);
+                    None
+                }
+            }
+        } else {
+            eprintln!("vita: '{}': No such file or directory", path.display());
+            None
+        }
+    };
+
+    let Some((left_name, left_content)) = read_one(&cli.files[0]) else {
+        process::exit(1);
+    };
+    let Some((right_name, right_content)) = read_one(&cli.files[1]) else {
+        process::exit(1);
+    };
+
+    let lang = cli.lang.clone().unwrap_or_else(|| {
+        let format = if cli.files[0].to_str() == Some("-") {
+            detect::detect_from_content(&left_content)
+        } else {
+            detect_format(&cli.files[0])
+        };
+        match format {
+            FileFormat::Code(l) => l,
+            FileFormat::Markdown => "Markdown".to_string(),
+            FileFormat::Json => "JSON".to_string(),
+            _ => "Plain Text".to_string(),
+        }
+    });
+
+    out.file_separator(&left_name, theme);
+    out.file_separator(&right_name, theme);
+
+    render::sidebyside::render(&left_content, &right_content, &lang, theme, out);
+}
+
+/// Reads `path`'s raw bytes for `--hex`/`--hex-diff`, treating `-` as
+/// stdin. Unlike the text-rendering paths, this never transcodes: a hex
+/// dump is exactly what `--hex` promises even for non-UTF-8 input.
+fn read_hex_input(path: &PathBuf) -> Option<(String, Vec<u8>)> {
+    if path.to_str() == Some("-") {
+        let mut buf = Vec::new();
+        if io::stdin().read_to_end(&mut buf).is_err() {
+            eprintln!("vita: failed to read stdin");
+            return None;
+        }
+        return Some(("stdin".to_string(), buf));
+    }
+
+    if !path.exists() {
+        eprintln!("vita: '{}': No such file or directory", path.display());
+        return None;
+    }
+
+    match std::fs::read(path) {
+        Ok(data) => Some((path.display().to_string(), data)),
+        Err(e) => {
+            eprintln!("vita: '{}': {}", path.display(), e);
+            None
+        }
+    }
+}
+
+fn run_hex(cli: &Cli, theme: &Theme, out: &Output) {
+    if let Some(other) = &cli.hex_diff {
+        let input = if cli.files.is_empty() { PathBuf::from("-") } else { cli.files[0].clone() };
+
+        let Some((left_name, left_data)) = read_hex_input(&input) else {
+            process::exit(1);
+        };
+        let Some((right_name, right_data)) = read_hex_input(other) else {
+            process::exit(1);
+        };
+
+        out.file_separator(&left_name, theme);
+        out.file_separator(&right_name, theme);
+        render::hex::render_diff(&left_data, &right_data, cli.head, cli.tail, true, theme, out);
+        return;
+    }
+
+    let search = cli.hex_grep.as_deref().map(|pat| {
+        let needle = match render::hex::parse_hex_pattern(pat) {
+            Some(bytes) => render::hex::Needle::Bytes(bytes),
+            None => render::hex::Needle::Text(pat.to_string()),
+        };
+        render::hex::SearchSpec { needle, only_matches: true, context: cli.context.unwrap_or(0) }
+    });
+
+    if cli.files.is_empty() {
+        if io::stdin().is_terminal() {
+            eprintln!("vita: no input. Use 'vita --help' for usage.");
+            process::exit(1);
+        }
+
+        let mut buf = Vec::new();
+        if io::stdin().read_to_end(&mut buf).is_err() {
+            eprintln!("vita: failed to read stdin");
+            process::exit(1);
+        }
+        render::hex::render(&buf, cli.head, cli.tail, search.as_ref(), theme, out);
+        return;
+    }
+
+    let multi = cli.files.len() > 1;
+    for path in &cli.files {
+        let Some((name, data)) = read_hex_input(path) else { continue };
+        if multi {
+            out.file_separator(&name, theme);
+        }
+        render::hex::render(&data, cli.head, cli.tail, search.as_ref(), theme, out);
+    }
+}
+
 fn run_brief_grep(cli: &Cli, pattern: &str, theme: &Theme, out: &Output) {
+    let re = match render::grep::compile(pattern, case_mode(cli)) {
+        Ok(re) => re,
+        Err(e) => {
+            eprintln!("vita: invalid --grep pattern '{}': {}", pattern, e);
+            process::exit(1);
+        }
+    };
+
     if cli.files.is_empty() {
         if io::stdin().is_terminal() {
             eprintln!("vita: no input. Use 'vita --help' for usage.");
@@ -361,9 +891,9 @@ fn run_brief_grep(cli: &Cli, pattern: &str, theme: &Theme, out: &Output) {
             .unwrap_or_else(|| detect::detect_from_content(&buf));
 
         if cli.info {
-            info::print_header(None, Some(&format), Some(&buf), theme, out);
+            info::print_header(None, Some(&format), Some(&buf), cli.icons, theme, out);
         }
-        render_brief_grep(&buf, &format, pattern, theme, out);
+        render_brief_grep(&buf, &format, &re, theme, out);
         return;
     }
 
@@ -376,9 +906,9 @@ fn run_brief_grep(cli: &Cli, pattern: &str, theme: &Theme, out: &Output) {
                 let buf = truncate_lines(&buf, cli.head, cli.tail);
                 let format = detect::detect_from_content(&buf);
                 if cli.info {
-                    info::print_header(None, Some(&format), Some(&buf), theme, out);
+                    info::print_header(None, Some(&format), Some(&buf), cli.icons, theme, out);
                 }
-                render_brief_grep(&buf, &format, pattern, theme, out);
+                render_brief_grep(&buf, &format, &re, theme, out);
             }
             continue;
         }
@@ -406,16 +936,16 @@ fn run_brief_grep(cli: &Cli, pattern: &str, theme: &Theme, out: &Output) {
             Ok(content) => {
                 let content = truncate_lines(&content, cli.head, cli.tail);
                 if cli.info {
-                    info::print_header(Some(path), Some(&format), Some(&content), theme, out);
+                    info::print_header(Some(path), Some(&format), Some(&content), cli.icons, theme, out);
                 }
-                render_brief_grep(&content, &format, pattern, theme, out);
+                render_brief_grep(&content, &format, &re, theme, out);
             }
             Err(e) => eprintln!("vita: '{}': {}", path.display(), e),
         }
     }
 }
 
-fn render_brief_grep(content: &str, format: &FileFormat, pattern: &str, theme: &Theme, out: &Output) {
+fn render_brief_grep(content: &str, format: &FileFormat, re: &regex::Regex, theme: &Theme, out: &Output) {
     let structural = render::brief::structural_lines(content, format);
 
     if structural.is_empty() {
@@ -428,24 +958,13 @@ fn render_brief_grep(content: &str, format: &FileFormat, pattern: &str, theme: &
     let num_width = format!("{}", total_lines).len();
 
     for (line_num, text) in &structural {
-        if !text.contains(pattern) {
+        if !re.is_match(text) {
             continue;
         }
 
-        out.dim(&format!(" {:>width$} â”‚ ", line_num, width = num_width), theme.line_number);
-
-        let mut rest = *text;
-        while let Some(pos) = rest.find(pattern) {
-            if pos > 0 {
-                out.colored(&rest[..pos], theme.text);
-            }
-            out.colored_bg(pattern, theme.grep_match_fg, theme.grep_match_bg);
-            rest = &rest[pos + pattern.len()..];
-        }
-        if !rest.is_empty() {
-            out.colored(rest, theme.text);
-        }
-        println!();
+        out.dim(&format!(" {:>width$} │ ", line_num, width = num_width), theme.line_number);
+        render::grep::highlight_matches(text, re, theme, out);
+        out.newline();
     }
 }
 
@@ -470,9 +989,9 @@ fn run_brief(cli: &Cli, theme: &Theme, out: &Output) {
             .unwrap_or_else(|| detect::detect_from_content(&buf));
 
         if cli.info {
-            info::print_header(None, Some(&format), Some(&buf), theme, out);
+            info::print_header(None, Some(&format), Some(&buf), cli.icons, theme, out);
         }
-        render::brief::render(&buf, &format, theme, out);
+        render_brief(&buf, &format, cli, theme, out);
         return;
     }
 
@@ -485,9 +1004,9 @@ fn run_brief(cli: &Cli, theme: &Theme, out: &Output) {
                 let buf = truncate_lines(&buf, cli.head, cli.tail);
                 let format = detect::detect_from_content(&buf);
                 if cli.info {
-                    info::print_header(None, Some(&format), Some(&buf), theme, out);
+                    info::print_header(None, Some(&format), Some(&buf), cli.icons, theme, out);
                 }
-                render::brief::render(&buf, &format, theme, out);
+                render_brief(&buf, &format, cli, theme, out);
             }
             continue;
         }
@@ -511,20 +1030,87 @@ fn run_brief(cli: &Cli, theme: &Theme, out: &Output) {
             continue;
         }
 
+        if cli.follow_mods && matches!(&format, FileFormat::Code(lang) if lang == "Rust") {
+            run_follow_mods(path, cli, theme, out);
+            continue;
+        }
+
         match std::fs::read_to_string(path) {
             Ok(content) => {
                 let content = truncate_lines(&content, cli.head, cli.tail);
                 if cli.info {
-                    info::print_header(Some(path), Some(&format), Some(&content), theme, out);
+                    info::print_header(Some(path), Some(&format), Some(&content), cli.icons, theme, out);
                 }
-                render::brief::render(&content, &format, theme, out);
+                render_brief(&content, &format, cli, theme, out);
             }
             Err(e) => eprintln!("vita: '{}': {}", path.display(), e),
         }
     }
 }
 
+/// Follows `entry`'s `mod foo;` declarations (see `modwalk`) and prints a
+/// `--brief` outline for every file reached, reporting ambiguous or missing
+/// modules to stderr without aborting the rest of the walk.
+fn run_follow_mods(entry: &PathBuf, cli: &Cli, theme: &Theme, out: &Output) {
+    let walk = modwalk::walk_crate(entry);
+    for err in &walk.errors {
+        eprintln!("vita: {}", err);
+    }
+
+    let multi = walk.files.len() > 1;
+    for file in &walk.files {
+        match std::fs::read_to_string(file) {
+            Ok(content) => {
+                let content = truncate_lines(&content, cli.head, cli.tail);
+                if multi {
+                    out.file_separator(&file.display().to_string(), theme);
+                }
+                let format = detect_format(file);
+                if cli.info {
+                    info::print_header(Some(file), Some(&format), Some(&content), cli.icons, theme, out);
+                }
+                render_brief(&content, &format, cli, theme, out);
+            }
+            Err(e) => eprintln!("vita: '{}': {}", file.display(), e),
+        }
+    }
+}
+
+/// Dispatches `--brief` to the JSON document-symbol serializer when
+/// `--format=json` is requested, otherwise the colored text outline.
+fn render_brief(content: &str, format: &FileFormat, cli: &Cli, theme: &Theme, out: &Output) {
+    if cli.format == "json" {
+        render::brief::render_json(content, format, out);
+    } else {
+        render::brief::render(content, format, theme, out);
+    }
+}
+
+/// Resolves the `-A`/`--before`/`-C` flags into (before, after) line counts.
+fn context_lines(cli: &Cli) -> (usize, usize) {
+    let before = cli.before.or(cli.context).unwrap_or(0);
+    let after = cli.after.or(cli.context).unwrap_or(0);
+    (before, after)
+}
+
+fn case_mode(cli: &Cli) -> render::grep::CaseMode {
+    if cli.case_sensitive {
+        render::grep::CaseMode::Sensitive
+    } else {
+        render::grep::CaseMode::Smart
+    }
+}
+
 fn run_grep(cli: &Cli, pattern: &str, theme: &Theme, out: &Output) {
+    let re = match render::grep::compile(pattern, case_mode(cli)) {
+        Ok(re) => re,
+        Err(e) => {
+            eprintln!("vita: invalid --grep pattern '{}': {}", pattern, e);
+            process::exit(1);
+        }
+    };
+    let (before, after) = context_lines(cli);
+
     if cli.files.is_empty() {
         if io::stdin().is_terminal() {
             eprintln!("vita: no input. Use 'vita --help' for usage.");
@@ -539,9 +1125,9 @@ fn run_grep(cli: &Cli, pattern: &str, theme: &Theme, out: &Output) {
 
         let buf = truncate_lines(&buf, cli.head, cli.tail);
         if cli.info {
-            info::print_header(None, None, Some(&buf), theme, out);
+            info::print_header(None, None, Some(&buf), cli.icons, theme, out);
         }
-        render::grep::render(&buf, pattern, theme, out);
+        render::grep::render(&buf, &re, before, after, theme, out);
         return;
     }
 
@@ -553,9 +1139,9 @@ fn run_grep(cli: &Cli, pattern: &str, theme: &Theme, out: &Output) {
             if io::stdin().read_to_string(&mut buf).is_ok() {
                 let buf = truncate_lines(&buf, cli.head, cli.tail);
                 if cli.info {
-                    info::print_header(None, None, Some(&buf), theme, out);
+                    info::print_header(None, None, Some(&buf), cli.icons, theme, out);
                 }
-                render::grep::render(&buf, pattern, theme, out);
+                render::grep::render(&buf, &re, before, after, theme, out);
             }
             continue;
         }
@@ -574,9 +1160,9 @@ fn run_grep(cli: &Cli, pattern: &str, theme: &Theme, out: &Output) {
                 let content = truncate_lines(&content, cli.head, cli.tail);
                 if cli.info {
                     let format = detect_format(path);
-                    info::print_header(Some(path), Some(&format), Some(&content), theme, out);
+                    info::print_header(Some(path), Some(&format), Some(&content), cli.icons, theme, out);
                 }
-                render::grep::render(&content, pattern, theme, out);
+                render::grep::render(&content, &re, before, after, theme, out);
             }
             Err(e) => eprintln!("vita: '{}': {}", path.display(), e),
         }
@@ -597,7 +1183,7 @@ fn truncate_lines(content: &str, head: Option<usize>, tail: Option<usize>) -> St
 
 fn render_content(content: &str, format: &FileFormat, cli: &Cli, theme: &Theme, out: &Output) {
     if cli.plain {
-        print!("{}", content);
+        out.raw(content);
         return;
     }
 
@@ -606,6 +1192,7 @@ fn render_content(content: &str, format: &FileFormat, cli: &Cli, theme: &Theme,
             FileFormat::Markdown => "Markdown",
             FileFormat::Json => "JSON",
             FileFormat::Csv => "Plain Text",
+            FileFormat::Org => "Org",
             FileFormat::Code(lang) => lang.as_str(),
             FileFormat::Plain => "Plain Text",
             FileFormat::Image => return,
@@ -615,11 +1202,24 @@ fn render_content(content: &str, format: &FileFormat, cli: &Cli, theme: &Theme,
     }
 
     match format {
-        FileFormat::Markdown => render::markdown::render(content, theme, out),
+        FileFormat::Markdown => render::markdown::render(content, theme, out, cli.toc),
         FileFormat::Json => render::json::render(content, theme, out),
         FileFormat::Csv => render::csv::render(content, theme, out),
+        FileFormat::Org => render::org::render(content, theme, out),
         FileFormat::Code(lang) => {
-            render::code::render(content, lang, cli.line_numbers, theme, out)
+            let registry = render::highlighter::Registry::with_defaults();
+            match registry.get(lang) {
+                Some(highlighter) => {
+                    for line in content.lines() {
+                        highlighter.render_line(line, theme, out);
+                        out.newline();
+                    }
+                }
+                None if lang.eq_ignore_ascii_case("yaml") || lang.eq_ignore_ascii_case("yml") => {
+                    render::yaml::render(content, theme, out)
+                }
+                None => render::code::render(content, lang, cli.line_numbers, theme, out),
+            }
         }
         FileFormat::Image => {}
         FileFormat::Plain => render::plain::render(content, cli.line_numbers, theme, out),
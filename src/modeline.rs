@@ -0,0 +1,149 @@
+//! Vim/Emacs modeline detection for `detect::detect_from_content`.
+//!
+//! Both editors let a file declare its own filetype inline, so piped or
+//! extensionless content that carries one of these can skip straight past
+//! the weaker Markdown/classifier guesses:
+//!
+//! ```text
+//! # vim: set ft=ruby:
+//! // -*- mode: python -*-
+//! ```
+//!
+//! Vim only honors modelines in the first/last `modelines` lines (5 by
+//! default), so this scans the same window. The declared mode/filetype is
+//! mapped through a small alias table onto the language name `detect_format`
+//! would have produced from an extension; `detect::syntax_fallback` picks up
+//! the ones syntect can't highlight natively at render time, same as always.
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::detect::FileFormat;
+
+/// Number of lines scanned from each end, matching Vim's default
+/// `'modelines'` setting.
+const SCAN_LINES: usize = 5;
+
+fn vim_re() -> &'static Regex {
+    static CELL: OnceLock<Regex> = OnceLock::new();
+    CELL.get_or_init(|| {
+        Regex::new(r"(?i)\b(?:vim|vi|ex):\s*(?:set\s+)?[^:\n]*?\b(?:ft|filetype)=([a-zA-Z0-9_+-]+)")
+            .expect("vim modeline pattern is valid")
+    })
+}
+
+fn emacs_re() -> &'static Regex {
+    static CELL: OnceLock<Regex> = OnceLock::new();
+    CELL.get_or_init(|| {
+        Regex::new(r"(?i)-\*-\s*(?:mode:\s*)?([a-zA-Z0-9_+-]+)\s*(?:;.*)?-\*-")
+            .expect("emacs modeline pattern is valid")
+    })
+}
+
+/// Scans the first/last `SCAN_LINES` lines of `content` for a Vim or Emacs
+/// modeline and resolves it to a `FileFormat::Code`. Returns `None` if no
+/// modeline is found or its declared mode isn't recognized.
+pub fn detect(content: &str) -> Option<FileFormat> {
+    let lines: Vec<&str> = content.lines().collect();
+    let head = lines.iter().take(SCAN_LINES);
+    let tail = lines.iter().rev().take(SCAN_LINES);
+
+    for line in head.chain(tail) {
+        if let Some(caps) = vim_re().captures(line) {
+            if let Some(lang) = alias(&caps[1]) {
+                return Some(FileFormat::Code(lang));
+            }
+        }
+        if let Some(caps) = emacs_re().captures(line) {
+            if let Some(lang) = alias(&caps[1]) {
+                return Some(FileFormat::Code(lang));
+            }
+        }
+    }
+
+    None
+}
+
+/// Maps a Vim `filetype`/Emacs `mode` name (case-insensitive) to the
+/// language name the rest of vita uses. Like `detect_format`, this stores
+/// the natural display name (e.g. `"TypeScript"`) even when syntect can't
+/// highlight it directly — `syntax_fallback` is consulted by the renderers
+/// at highlight time, not here.
+fn alias(name: &str) -> Option<String> {
+    let lang = match name.to_lowercase().as_str() {
+        "rust" | "rust-mode" => "Rust",
+        "python" | "python-mode" | "py" => "Python",
+        "javascript" | "js" | "js2" | "js2-mode" | "javascript-mode" => "JavaScript",
+        "typescript" | "ts" | "typescript-mode" => "TypeScript",
+        "go" | "golang" | "go-mode" => "Go",
+        "ruby" | "rb" | "ruby-mode" => "Ruby",
+        "c" | "c-mode" => "C",
+        "cpp" | "c++" | "cplusplus" | "c++-mode" => "C++",
+        "java" | "java-mode" => "Java",
+        "php" => "PHP",
+        "cs" | "csharp" => "C#",
+        "lua" | "lua-mode" => "Lua",
+        "haskell" | "haskell-mode" => "Haskell",
+        "perl" | "perl-mode" | "cperl-mode" => "Perl",
+        "sh" | "bash" | "zsh" | "shell-script" | "shell-script-mode" => "Bash",
+        "fish" => "Fish",
+        "yaml" | "yaml-mode" => "YAML",
+        "json" | "json-mode" | "jsonc" => "JSON",
+        "html" | "html-mode" => "HTML",
+        "css" | "css-mode" => "CSS",
+        "sql" | "sql-mode" => "SQL",
+        "xml" | "nxml-mode" => "XML",
+        "toml" | "toml-mode" => "TOML",
+        "dockerfile" => "Dockerfile",
+        "zig" => "Zig",
+        "swift" => "Swift",
+        "kotlin" | "kt" => "Kotlin",
+        "scala" | "scala-mode" => "Scala",
+        "elixir" | "elixir-mode" => "Elixir",
+        _ => return None,
+    };
+
+    Some(lang.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vim_modeline_trailing() {
+        let content = "puts 'hi'\n# vim: set ft=ruby:\n";
+        assert!(matches!(detect(content), Some(FileFormat::Code(ref l)) if l == "Ruby"));
+    }
+
+    #[test]
+    fn test_vim_modeline_short_form() {
+        let content = "print('hi')\n# vim: ft=python\n";
+        assert!(matches!(detect(content), Some(FileFormat::Code(ref l)) if l == "Python"));
+    }
+
+    #[test]
+    fn test_emacs_modeline_first_line() {
+        let content = "// -*- mode: c++ -*-\nint main() {}\n";
+        assert!(matches!(detect(content), Some(FileFormat::Code(ref l)) if l == "C++"));
+    }
+
+    #[test]
+    fn test_emacs_modeline_bare_name() {
+        let content = "# -*- python -*-\nprint('hi')\n";
+        assert!(matches!(detect(content), Some(FileFormat::Code(ref l)) if l == "Python"));
+    }
+
+    #[test]
+    fn test_no_modeline() {
+        let content = "just a plain file\nwith no modeline at all\n";
+        assert!(detect(content).is_none());
+    }
+
+    #[test]
+    fn test_unrecognized_mode_is_none() {
+        let content = "# vim: set ft=some-unknown-dsl:\n";
+        assert!(detect(content).is_none());
+    }
+}
@@ -0,0 +1,207 @@
+//! Rust `mod` declaration resolution (`--follow-mods`)
+//!
+//! Turns a single entry file into the set of files reachable by following
+//! its `mod foo;` declarations, mirroring rustc's own module resolution:
+//! `foo.rs` or `foo/mod.rs` relative to the declaring file's module
+//! directory, or the path named by a `#[path = "..."]` attribute. Inline
+//! `mod foo { ... }` blocks have no backing file and are not followed.
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub enum ModuleWalkError {
+    /// Both `foo.rs` and `foo/mod.rs` exist for the same `mod foo;` — rustc
+    /// treats this as a hard error rather than picking one.
+    Ambiguous {
+        module: String,
+        flat: PathBuf,
+        nested: PathBuf,
+    },
+    /// Neither the default candidates nor a `#[path = "..."]` override
+    /// resolved to an existing file.
+    NotFound { module: String, dir: PathBuf },
+}
+
+impl fmt::Display for ModuleWalkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModuleWalkError::Ambiguous { module, flat, nested } => write!(
+                f,
+                "ambiguous module `{}`: both {} and {} exist",
+                module,
+                flat.display(),
+                nested.display()
+            ),
+            ModuleWalkError::NotFound { module, dir } => write!(
+                f,
+                "file not found for module `{}` in {}",
+                module,
+                dir.display()
+            ),
+        }
+    }
+}
+
+pub struct WalkResult {
+    /// Canonicalized paths of every file reached, in discovery order.
+    pub files: Vec<PathBuf>,
+    pub errors: Vec<ModuleWalkError>,
+}
+
+/// Walks `mod foo;` declarations starting from `entry`, returning every
+/// file reached plus any ambiguous/missing module diagnostics. One bad
+/// `mod` declaration is recorded as an error and does not abort the walk.
+pub fn walk_crate(entry: &Path) -> WalkResult {
+    let mut files = Vec::new();
+    let mut errors = Vec::new();
+
+    let Ok(canon_entry) = entry.canonicalize() else {
+        errors.push(ModuleWalkError::NotFound {
+            module: entry.display().to_string(),
+            dir: PathBuf::new(),
+        });
+        return WalkResult { files, errors };
+    };
+
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    let mut worklist: VecDeque<PathBuf> = VecDeque::new();
+    visited.insert(canon_entry.clone());
+    worklist.push_back(canon_entry);
+
+    while let Some(path) = worklist.pop_front() {
+        let Ok(source) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let dir = module_dir(&path);
+
+        for (module, path_attr) in find_mod_decls(&source) {
+            match resolve_module(&dir, &module, path_attr.as_deref()) {
+                Ok(resolved) => match resolved.canonicalize() {
+                    Ok(canon) => {
+                        if visited.insert(canon.clone()) {
+                            worklist.push_back(canon);
+                        }
+                    }
+                    Err(_) => errors.push(ModuleWalkError::NotFound { module, dir: dir.clone() }),
+                },
+                Err(e) => errors.push(e),
+            }
+        }
+
+        files.push(path);
+    }
+
+    WalkResult { files, errors }
+}
+
+/// The directory a file's `mod` declarations resolve children against.
+/// Crate roots (`main.rs`/`lib.rs`) and directory modules (`mod.rs`) search
+/// their own directory; an ordinary `foo.rs` searches the `foo/` directory.
+fn module_dir(path: &Path) -> PathBuf {
+    let file_name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+    match file_name {
+        "mod.rs" | "main.rs" | "lib.rs" => parent.to_path_buf(),
+        _ => {
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+            parent.join(stem)
+        }
+    }
+}
+
+fn resolve_module(
+    dir: &Path,
+    module: &str,
+    path_attr: Option<&str>,
+) -> Result<PathBuf, ModuleWalkError> {
+    if let Some(p) = path_attr {
+        let candidate = if Path::new(p).is_absolute() {
+            PathBuf::from(p)
+        } else {
+            dir.join(p)
+        };
+        return if candidate.exists() {
+            Ok(candidate)
+        } else {
+            Err(ModuleWalkError::NotFound {
+                module: module.to_string(),
+                dir: dir.to_path_buf(),
+            })
+        };
+    }
+
+    let flat = dir.join(format!("{}.rs", module));
+    let nested = dir.join(module).join("mod.rs");
+
+    match (flat.exists(), nested.exists()) {
+        (true, true) => Err(ModuleWalkError::Ambiguous {
+            module: module.to_string(),
+            flat,
+            nested,
+        }),
+        (true, false) => Ok(flat),
+        (false, true) => Ok(nested),
+        (false, false) => Err(ModuleWalkError::NotFound {
+            module: module.to_string(),
+            dir: dir.to_path_buf(),
+        }),
+    }
+}
+
+/// Scans `source` line-by-line for file-backed `mod foo;` declarations,
+/// pairing each with the `#[path = "..."]` override on the line directly
+/// above it, if any. `mod foo { ... }` (inline, no semicolon) is skipped.
+fn find_mod_decls(source: &str) -> Vec<(String, Option<String>)> {
+    let mut decls = Vec::new();
+    let mut pending_path: Option<String> = None;
+
+    for line in source.lines() {
+        let trimmed = line.trim();
+
+        if let Some(p) = parse_path_attr(trimmed) {
+            pending_path = Some(p);
+            continue;
+        }
+
+        if let Some(name) = parse_mod_decl(trimmed) {
+            decls.push((name, pending_path.take()));
+            continue;
+        }
+
+        if !trimmed.is_empty() {
+            pending_path = None;
+        }
+    }
+
+    decls
+}
+
+fn parse_path_attr(trimmed: &str) -> Option<String> {
+    let rest = trimmed.strip_prefix("#[path")?.trim_start();
+    let rest = rest.strip_prefix('=')?.trim_start();
+    let rest = rest.strip_prefix('"')?;
+    let end = rest.find('"')?;
+    Some(rest[..end].to_string())
+}
+
+fn parse_mod_decl(trimmed: &str) -> Option<String> {
+    let mut rest = trimmed;
+    if let Some(r) = rest.strip_prefix("pub(crate)") {
+        rest = r.trim_start();
+    } else if let Some(r) = rest.strip_prefix("pub(super)") {
+        rest = r.trim_start();
+    } else if let Some(r) = rest.strip_prefix("pub") {
+        rest = r.trim_start();
+    }
+
+    let rest = rest.strip_prefix("mod ")?.trim_start();
+    let end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_'))?;
+    let (name, after) = rest.split_at(end);
+    if name.is_empty() || !after.trim_start().starts_with(';') {
+        return None;
+    }
+    Some(name.to_string())
+}
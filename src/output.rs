@@ -1,112 +1,517 @@
 use crossterm::style::{self, Color, Stylize};
-use std::io::{self, Write};
+use std::cell::RefCell;
+use std::io::{self, IsTerminal, Read, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
 
 use crate::theme::Theme;
 
+/// Controls whether long output is piped through a pager, mirroring `bat`'s
+/// `--paging` flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Paging {
+    /// Page only when stdout is a terminal and the content is taller than
+    /// one screen.
+    Auto,
+    Always,
+    Never,
+}
+
+impl Paging {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "auto" => Some(Paging::Auto),
+            "always" => Some(Paging::Always),
+            "never" => Some(Paging::Never),
+            _ => None,
+        }
+    }
+}
+
+/// How many colors the terminal can actually display, detected from
+/// `$COLORTERM`/`$TERM` so truecolor output can be downgraded to something
+/// legible over links that don't pass 24-bit color through (many SSH/tmux
+/// setups).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+impl ColorDepth {
+    /// Reads `$COLORTERM` (`truecolor`/`24bit` means 24-bit color is safe),
+    /// then `$TERM` (a `256color` suffix means the 256-color palette is
+    /// safe), defaulting to the standard 16-color ANSI palette when neither
+    /// gives a clear signal.
+    pub fn detect() -> Self {
+        if let Ok(colorterm) = std::env::var("COLORTERM") {
+            if colorterm == "truecolor" || colorterm == "24bit" {
+                return ColorDepth::TrueColor;
+            }
+        }
+        match std::env::var("TERM") {
+            Ok(term) if term.contains("256color") => ColorDepth::Ansi256,
+            Ok(term) if term.contains("direct") => ColorDepth::TrueColor,
+            _ => ColorDepth::Ansi16,
+        }
+    }
+}
+
+/// Downgrades an RGB color to the nearest representable color at `depth`;
+/// non-RGB colors (already a named/indexed variant) pass through unchanged.
+pub(crate) fn downgrade(color: Color, depth: ColorDepth) -> Color {
+    let Color::Rgb { r, g, b } = color else {
+        return color;
+    };
+    match depth {
+        ColorDepth::TrueColor => color,
+        ColorDepth::Ansi256 => Color::AnsiValue(nearest_256(r, g, b)),
+        ColorDepth::Ansi16 => ANSI16_PALETTE[nearest_ansi16_index(r, g, b) as usize],
+    }
+}
+
+/// Nearest xterm 256-color palette index: the 6×6×6 color cube and the
+/// 24-step grayscale ramp each produce a candidate, and whichever is closer
+/// in squared RGB distance wins.
+pub(crate) fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let cube_step = |c: u8| -> u8 {
+        if c <= 48 {
+            0
+        } else {
+            (((c as f32 - 55.0) / 40.0).round().clamp(0.0, 5.0)) as u8
+        }
+    };
+    let cube_value = |step: u8| -> u8 {
+        if step == 0 {
+            0
+        } else {
+            55 + step * 40
+        }
+    };
+
+    let (rs, gs, bs) = (cube_step(r), cube_step(g), cube_step(b));
+    let cube_idx = 16 + 36 * rs + 6 * gs + bs;
+    let cube_rgb = (cube_value(rs), cube_value(gs), cube_value(bs));
+
+    let gray = ((r as u32 + g as u32 + b as u32) / 3) as u8;
+    let gray_step = if gray < 8 {
+        0
+    } else {
+        (((gray as f32 - 8.0) / 10.0).round().clamp(0.0, 23.0)) as u8
+    };
+    let gray_idx = 232 + gray_step;
+    let gray_val = (8 + gray_step as u32 * 10) as u8;
+
+    let dist2 = |c: (u8, u8, u8)| -> i32 {
+        let dr = r as i32 - c.0 as i32;
+        let dg = g as i32 - c.1 as i32;
+        let db = b as i32 - c.2 as i32;
+        dr * dr + dg * dg + db * db
+    };
+
+    if dist2(cube_rgb) <= dist2((gray_val, gray_val, gray_val)) {
+        cube_idx
+    } else {
+        gray_idx
+    }
+}
+
+/// Standard 16-color ANSI palette, in the usual black/red/green/.../white
+/// then bright-variant order, used both to resolve `ANSI16_PALETTE` colors
+/// and as the reference points for `nearest_ansi16_index`.
+const ANSI16_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (128, 0, 0),
+    (0, 128, 0),
+    (128, 128, 0),
+    (0, 0, 128),
+    (128, 0, 128),
+    (0, 128, 128),
+    (192, 192, 192),
+    (128, 128, 128),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (0, 0, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+const ANSI16_PALETTE: [Color; 16] = [
+    Color::Black,
+    Color::DarkRed,
+    Color::DarkGreen,
+    Color::DarkYellow,
+    Color::DarkBlue,
+    Color::DarkMagenta,
+    Color::DarkCyan,
+    Color::Grey,
+    Color::DarkGrey,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+];
+
+/// Index (0-15) into `ANSI16_RGB`/`ANSI16_PALETTE` nearest `(r, g, b)` by
+/// squared distance.
+pub(crate) fn nearest_ansi16_index(r: u8, g: u8, b: u8) -> u8 {
+    ANSI16_RGB
+        .iter()
+        .enumerate()
+        .min_by_key(|&(_, &(pr, pg, pb))| {
+            let dr = r as i32 - pr as i32;
+            let dg = g as i32 - pg as i32;
+            let db = b as i32 - pb as i32;
+            dr * dr + dg * dg + db * db
+        })
+        .map(|(i, _)| i as u8)
+        .unwrap_or(0)
+}
+
 pub struct Output {
     pub use_colors: bool,
     pub term_width: u16,
+    pub color_depth: ColorDepth,
+    sink: RefCell<Box<dyn Write>>,
+    pager: Option<Child>,
 }
 
 impl Output {
     pub fn new(use_colors: bool) -> Self {
+        Self::with_paging(use_colors, Paging::Never)
+    }
+
+    /// Builds an `Output`, spawning a `less` pager as the write sink when
+    /// `paging` calls for it. `less --quit-if-one-screen` itself decides
+    /// whether the content is actually taller than the terminal, so
+    /// `Paging::Auto` doesn't need to pre-count lines — it just needs to
+    /// know stdout is a terminal worth paging at all.
+    pub fn with_paging(use_colors: bool, paging: Paging) -> Self {
         let term_width = terminal_size::terminal_size()
             .map(|(w, _)| w.0)
             .unwrap_or(80);
 
+        let is_tty = {
+            use std::io::IsTerminal;
+            io::stdout().is_terminal()
+        };
+        let should_page = match paging {
+            Paging::Always | Paging::Auto => is_tty,
+            Paging::Never => false,
+        };
+
+        // Only `Auto` lets `less` exit straight away when the content fits
+        // on one screen; `Always` keeps the pager open regardless.
+        let less_args: &[&str] = if paging == Paging::Always {
+            &["-R", "--no-init"]
+        } else {
+            &["-R", "--quit-if-one-screen", "--no-init"]
+        };
+
+        let mut pager = None;
+        let sink: Box<dyn Write> = if should_page {
+            match Command::new("less")
+                .args(less_args)
+                .stdin(Stdio::piped())
+                .spawn()
+            {
+                Ok(mut child) => {
+                    let stdin = child.stdin.take().expect("piped stdin");
+                    pager = Some(child);
+                    Box::new(stdin)
+                }
+                Err(_) => Box::new(io::stdout()),
+            }
+        } else {
+            Box::new(io::stdout())
+        };
+
         Self {
             use_colors,
             term_width,
+            color_depth: ColorDepth::detect(),
+            sink: RefCell::new(sink),
+            pager,
         }
     }
 
+    /// Downgrades `color` to whatever this terminal can actually display.
+    fn adapt(&self, color: Color) -> Color {
+        downgrade(color, self.color_depth)
+    }
+
+    /// A raw `\x1b[38;...m` foreground escape for `(r, g, b)`, downgraded to
+    /// this terminal's `color_depth` the same way `colored` is. For callers
+    /// that need to hand-roll ANSI (e.g. painting a code block's background
+    /// across fragments without crossterm resetting it in between).
+    pub fn ansi_fg(&self, r: u8, g: u8, b: u8) -> String {
+        format!("{}", style::SetForegroundColor(self.adapt(Color::Rgb { r, g, b })))
+    }
+
+    /// A raw `\x1b[48;...m` background escape for `(r, g, b)`; see `ansi_fg`.
+    pub fn ansi_bg(&self, r: u8, g: u8, b: u8) -> String {
+        format!("{}", style::SetBackgroundColor(self.adapt(Color::Rgb { r, g, b })))
+    }
+
+    /// Writes `text` verbatim to the sink, with no trailing newline.
+    pub fn raw(&self, text: &str) {
+        let _ = write!(self.sink.borrow_mut(), "{}", text);
+    }
+
+    /// Writes a single newline to the sink.
+    pub fn newline(&self) {
+        let _ = writeln!(self.sink.borrow_mut());
+    }
+
     pub fn colored(&self, text: &str, color: Color) {
         if self.use_colors {
-            print!("{}", style::style(text).with(color));
+            self.raw(&format!("{}", style::style(text).with(self.adapt(color))));
         } else {
-            print!("{}", text);
+            self.raw(text);
         }
     }
 
     pub fn bold_colored(&self, text: &str, color: Color) {
         if self.use_colors {
-            print!("{}", style::style(text).with(color).bold());
+            self.raw(&format!("{}", style::style(text).with(self.adapt(color)).bold()));
         } else {
-            print!("{}", text);
+            self.raw(text);
         }
     }
 
     pub fn italic_colored(&self, text: &str, color: Color) {
         if self.use_colors {
-            print!("{}", style::style(text).with(color).italic());
+            self.raw(&format!("{}", style::style(text).with(self.adapt(color)).italic()));
         } else {
-            print!("{}", text);
+            self.raw(text);
         }
     }
 
     pub fn underline_colored(&self, text: &str, color: Color) {
         if self.use_colors {
-            print!("{}", style::style(text).with(color).underlined());
+            self.raw(&format!("{}", style::style(text).with(self.adapt(color)).underlined()));
         } else {
-            print!("{}", text);
+            self.raw(text);
         }
     }
 
     pub fn strike_colored(&self, text: &str, color: Color) {
         if self.use_colors {
-            print!("{}", style::style(text).with(color).crossed_out());
+            self.raw(&format!("{}", style::style(text).with(self.adapt(color)).crossed_out()));
         } else {
-            print!("{}", text);
+            self.raw(text);
         }
     }
 
     pub fn dim(&self, text: &str, color: Color) {
         if self.use_colors {
-            print!("{}", style::style(text).with(color).dim());
+            self.raw(&format!("{}", style::style(text).with(self.adapt(color)).dim()));
         } else {
-            print!("{}", text);
+            self.raw(text);
         }
     }
 
     pub fn colored_bg(&self, text: &str, fg: Color, bg: Color) {
         if self.use_colors {
-            print!("{}", style::style(text).with(fg).on(bg));
+            self.raw(&format!("{}", style::style(text).with(self.adapt(fg)).on(self.adapt(bg))));
         } else {
-            print!("{}", text);
+            self.raw(text);
         }
     }
 
     pub fn hyperlink_start(&self, url: &str) {
         if self.use_colors {
-            print!("\x1b]8;;{}\x1b\\", url);
+            self.raw(&format!("\x1b]8;;{}\x1b\\", url));
         }
     }
 
     pub fn hyperlink_end(&self) {
         if self.use_colors {
-            print!("\x1b]8;;\x1b\\");
+            self.raw("\x1b]8;;\x1b\\");
         }
     }
 
     #[allow(dead_code)]
     pub fn reset(&self) {
         if self.use_colors {
-            print!("{}", style::style("").reset());
+            self.raw(&format!("{}", style::style("").reset()));
         }
     }
 
     /// File separator for multi-file output
     pub fn file_separator(&self, filename: &str, theme: &Theme) {
-        println!();
+        self.newline();
         self.colored("━━━ ", theme.hr);
         self.bold_colored(filename, theme.file_header);
         self.colored(" ━━━", theme.hr);
-        println!();
-        println!();
+        self.newline();
+        self.newline();
     }
 
-    #[allow(dead_code)]
     pub fn flush(&self) {
+        let _ = self.sink.borrow_mut().flush();
+    }
+
+    /// Queries the terminal's real background color via OSC 11
+    /// (`ESC]11;?BEL`), behind raw mode so the reply isn't echoed. Parses a
+    /// reply of the form `ESC]11;rgb:RRRR/GGGG/BBBB` (ST- or BEL-terminated)
+    /// down to 8-bit channels. Returns `None` if stdout isn't a terminal, raw
+    /// mode can't be entered, or nothing recognizable comes back within the
+    /// timeout — callers should fall back to a theme-provided default.
+    pub fn query_terminal_bg(&self) -> Option<(u8, u8, u8)> {
+        if !io::stdout().is_terminal() {
+            return None;
+        }
+        if crossterm::terminal::enable_raw_mode().is_err() {
+            return None;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut response = Vec::new();
+            let mut byte = [0u8; 1];
+            let mut stdin = io::stdin();
+            while response.len() < 64 {
+                match stdin.read(&mut byte) {
+                    Ok(1) => {
+                        response.push(byte[0]);
+                        let is_st = byte[0] == b'\\'
+                            && response.len() >= 2
+                            && response[response.len() - 2] == 0x1b;
+                        if byte[0] == 0x07 || is_st {
+                            break;
+                        }
+                    }
+                    _ => break,
+                }
+            }
+            let _ = tx.send(response);
+        });
+
+        print!("\x1b]11;?\x07");
         let _ = io::stdout().flush();
+
+        let result = match rx.recv_timeout(Duration::from_millis(200)) {
+            Ok(response) => parse_osc11_bg(&String::from_utf8_lossy(&response)),
+            Err(_) => None,
+        };
+
+        let _ = crossterm::terminal::disable_raw_mode();
+        result
+    }
+}
+
+/// Parses the `rgb:RRRR/GGGG/BBBB` body of an OSC 11 reply, scaling each
+/// channel (1-4 hex digits) proportionally down to 8 bits.
+fn parse_osc11_bg(reply: &str) -> Option<(u8, u8, u8)> {
+    let body = reply.split("rgb:").nth(1)?;
+    let mut channels = body.splitn(3, '/');
+    let r = parse_channel(channels.next()?)?;
+    let g = parse_channel(channels.next()?)?;
+    let b = parse_channel(channels.next()?)?;
+    Some((r, g, b))
+}
+
+fn parse_channel(hex: &str) -> Option<u8> {
+    let hex: String = hex.chars().take_while(|c| c.is_ascii_hexdigit()).collect();
+    if hex.is_empty() {
+        return None;
+    }
+    let value = u32::from_str_radix(&hex, 16).ok()?;
+    let max = (1u32 << (hex.len() * 4)) - 1;
+    Some((value * 255 / max) as u8)
+}
+
+impl Drop for Output {
+    fn drop(&mut self) {
+        let _ = self.sink.borrow_mut().flush();
+        if let Some(mut child) = self.pager.take() {
+            // Replace the sink so the pager's stdin pipe (its only
+            // reference) is dropped and closed, signalling EOF, before we
+            // wait — `less` won't exit otherwise.
+            *self.sink.borrow_mut() = Box::new(io::sink());
+            let _ = child.wait();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_osc11_bg_st_terminated() {
+        let reply = "\x1b]11;rgb:1a1a/1a1a/2e2e\x1b\\";
+        assert_eq!(parse_osc11_bg(reply), Some((0x1a, 0x1a, 0x2e)));
+    }
+
+    #[test]
+    fn test_parse_osc11_bg_bel_terminated() {
+        let reply = "\x1b]11;rgb:ffff/ffff/ffff\x07";
+        assert_eq!(parse_osc11_bg(reply), Some((255, 255, 255)));
+    }
+
+    #[test]
+    fn test_parse_osc11_bg_missing() {
+        assert_eq!(parse_osc11_bg("\x1b[c"), None);
+    }
+
+    #[test]
+    fn test_parse_channel_eight_bit() {
+        assert_eq!(parse_channel("ff"), Some(255));
+        assert_eq!(parse_channel("00"), Some(0));
+    }
+
+    #[test]
+    fn test_nearest_256_pure_black_and_white() {
+        assert_eq!(nearest_256(0, 0, 0), 16);
+        assert_eq!(nearest_256(255, 255, 255), 231);
+    }
+
+    #[test]
+    fn test_nearest_256_prefers_gray_ramp_for_grays() {
+        // A mid gray sits closer to the 24-step grayscale ramp than to any
+        // cube corner.
+        let idx = nearest_256(118, 118, 118);
+        assert!((232..=255).contains(&idx));
+    }
+
+    #[test]
+    fn test_nearest_ansi16_index_primaries() {
+        assert_eq!(nearest_ansi16_index(0, 0, 0), 0);
+        assert_eq!(nearest_ansi16_index(255, 0, 0), 9);
+        assert_eq!(nearest_ansi16_index(255, 255, 255), 15);
+    }
+
+    #[test]
+    fn test_downgrade_passes_non_rgb_through() {
+        assert_eq!(downgrade(Color::Red, ColorDepth::Ansi256), Color::Red);
+    }
+
+    #[test]
+    fn test_downgrade_truecolor_is_identity() {
+        let c = Color::Rgb { r: 10, g: 20, b: 30 };
+        assert_eq!(downgrade(c, ColorDepth::TrueColor), c);
+    }
+
+    #[test]
+    fn test_ansi_fg_downgrades_to_256() {
+        let mut out = Output::new(true);
+        out.color_depth = ColorDepth::Ansi256;
+        assert_eq!(out.ansi_fg(0, 0, 0), format!("\x1b[38;5;{}m", nearest_256(0, 0, 0)));
+    }
+
+    #[test]
+    fn test_ansi_bg_truecolor_passthrough() {
+        let mut out = Output::new(true);
+        out.color_depth = ColorDepth::TrueColor;
+        assert_eq!(out.ansi_bg(10, 20, 30), "\x1b[48;2;10;20;30m");
     }
 }
@@ -11,10 +11,10 @@ use std::time::{Duration, UNIX_EPOCH};
 
 use crossterm::style::Color;
 use syntect::easy::HighlightLines;
-use syntect::highlighting::{FontStyle, Style, ThemeSet};
-use syntect::parsing::SyntaxSet;
+use syntect::highlighting::{FontStyle, Style};
 
 use crate::output::Output;
+use crate::render::syntax::{find_syntax, theme_set};
 use crate::theme::Theme;
 
 struct BlameLine {
@@ -67,19 +67,8 @@ pub fn render(
     let line_count = lines.len();
     let num_width = format!("{}", line_count).len();
 
-    let ss = SyntaxSet::load_defaults_newlines();
-    let ts = ThemeSet::load_defaults();
-
-    let syntax = ss
-        .find_syntax_by_name(lang)
-        .or_else(|| ss.find_syntax_by_extension(&lang.to_lowercase()))
-        .or_else(|| ss.find_syntax_by_token(&lang.to_lowercase()))
-        .or_else(|| {
-            let fb = crate::detect::syntax_fallback(lang);
-            ss.find_syntax_by_name(fb)
-                .or_else(|| ss.find_syntax_by_token(fb))
-        })
-        .unwrap_or_else(|| ss.find_syntax_plain_text());
+    let ts = theme_set();
+    let (ss, syntax) = find_syntax(lang);
 
     let highlight_theme = ts
         .themes
@@ -104,15 +93,15 @@ pub fn render(
 
         if same_commit {
             let meta_width = 7 + 2 + max_author + 2 + max_date;
-            print!("{:width$}", "", width = meta_width);
+            out.raw(&format!("{:width$}", "", width = meta_width));
         } else {
             out.colored(&line.hash, theme.blame_hash);
-            print!("  ");
+            out.raw("  ");
             out.colored(
                 &format!("{:<width$}", line.author, width = max_author),
                 theme.blame_author,
             );
-            print!("  ");
+            out.raw("  ");
             out.dim(&format!("{:<width$}", dates[i], width = max_date), theme.blame_date);
         }
 
@@ -122,7 +111,7 @@ pub fn render(
         );
 
         let code_line = format!("{}\n", line.content);
-        match h.highlight_line(&code_line, &ss) {
+        match h.highlight_line(&code_line, ss) {
             Ok(ranges) => {
                 for (style, text) in ranges {
                     let color = syntect_to_crossterm(style);
@@ -135,7 +124,7 @@ pub fn render(
                     }
                 }
             }
-            Err(_) => print!("{}\n", line.content),
+            Err(_) => out.raw(&format!("{}\n", line.content)),
         }
 
         prev_hash.clone_from(&line.hash);
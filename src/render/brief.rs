@@ -5,23 +5,50 @@
 //! Uses keyword-based line matching for code and format-specific logic for
 //! data files (JSON, CSV, YAML, TOML, Markdown, HTML).
 
+use serde::Deserialize;
+
 use crate::detect::FileFormat;
 use crate::output::Output;
 use crate::theme::Theme;
 
 pub fn render(content: &str, format: &FileFormat, theme: &Theme, out: &Output) {
     match format {
-        FileFormat::Markdown => brief_markdown(content, theme, out),
+        FileFormat::Markdown => {
+            if !render_nested(content, format, theme, out) {
+                brief_markdown(content, theme, out);
+            }
+        }
         FileFormat::Json => brief_json(content, theme, out),
         FileFormat::Csv => brief_csv(content, theme, out),
-        FileFormat::Toml => brief_toml(content, theme, out),
-        FileFormat::Yaml => brief_yaml(content, theme, out),
+        FileFormat::Code(lang) if lang.eq_ignore_ascii_case("TOML") => {
+            if !render_nested(content, format, theme, out) {
+                brief_toml(content, theme, out);
+            }
+        }
+        FileFormat::Code(lang) if lang.eq_ignore_ascii_case("YAML") => {
+            if !render_nested(content, format, theme, out) {
+                brief_yaml(content, theme, out);
+            }
+        }
         FileFormat::Code(lang) => brief_code(content, lang, theme, out),
+        FileFormat::Org => brief_org(content, theme, out),
         FileFormat::Plain => brief_plain(content, theme, out),
         FileFormat::Image => {}
     }
 }
 
+/// Tries the nested outline tree first; returns `false` (so the caller can
+/// fall back to the flat scanner) when there's nothing to nest.
+fn render_nested(content: &str, format: &FileFormat, theme: &Theme, out: &Output) -> bool {
+    let tree = structural_tree(content, format);
+    if tree.is_empty() {
+        return false;
+    }
+    let width = line_num_width(content.lines().count());
+    render_tree(&tree, 0, width, theme, out);
+    true
+}
+
 // ─── Markdown ───
 
 fn brief_markdown(content: &str, theme: &Theme, out: &Output) {
@@ -79,6 +106,23 @@ fn brief_json_value(val: &serde_json::Value, theme: &Theme, out: &Output) {
     }
 }
 
+/// Truncates `s` to at most `max_bytes` bytes, cutting at the last char
+/// boundary at or before that offset rather than a fixed byte index — a
+/// plain `&s[..max_bytes]` panics if it lands inside a multi-byte
+/// character.
+fn truncate_at_char_boundary(s: &str, max_bytes: usize) -> &str {
+    if s.len() <= max_bytes {
+        return s;
+    }
+    let end = s
+        .char_indices()
+        .map(|(i, _)| i)
+        .take_while(|&i| i <= max_bytes)
+        .last()
+        .unwrap_or(0);
+    &s[..end]
+}
+
 fn json_type_summary(val: &serde_json::Value) -> String {
     match val {
         serde_json::Value::Object(map) => format!("{{}} ({} keys)", map.len()),
@@ -91,7 +135,7 @@ fn json_type_summary(val: &serde_json::Value) -> String {
         }
         serde_json::Value::String(s) => {
             if s.len() > 40 {
-                format!("\"{}...\"", &s[..37])
+                format!("\"{}...\"", truncate_at_char_boundary(s, 37))
             } else {
                 format!("\"{}\"", s)
             }
@@ -127,7 +171,7 @@ fn brief_csv(content: &str, theme: &Theme, out: &Output) {
     let cols: Vec<&str> = header.split(delimiter).map(|s| s.trim()).collect();
     out.bold_colored("  Columns: ", theme.table_header);
     out.colored(&cols.join(", "), theme.text);
-    println!();
+    out.newline();
 
     let data_lines: Vec<&str> = lines.filter(|l| !l.trim().is_empty()).collect();
     let total = data_lines.len();
@@ -137,7 +181,7 @@ fn brief_csv(content: &str, theme: &Theme, out: &Output) {
         let fields: Vec<&str> = line.split(delimiter).map(|s| s.trim()).collect();
         out.dim("  ", theme.line_number);
         out.colored(&fields.join(", "), theme.text);
-        println!();
+        out.newline();
     }
 
     if total > 3 {
@@ -162,6 +206,22 @@ fn brief_code(content: &str, lang: &str, theme: &Theme, out: &Output) {
         _ => {}
     }
 
+    if let Some(symbols) = crate::symbols::extract_symbols(content, &normalized) {
+        if symbols.is_empty() {
+            out.dim(&format!("  (no brief outline for {})\n", lang), theme.line_number);
+        } else {
+            let width = line_num_width(content.lines().count());
+            for sym in &symbols {
+                print_symbol(sym, width, theme, out);
+            }
+        }
+        return;
+    }
+
+    if render_nested(content, &FileFormat::Code(lang.to_string()), theme, out) {
+        return;
+    }
+
     let keywords = keywords_for(&normalized);
     let lines: Vec<&str> = content.lines().collect();
     let width = line_num_width(lines.len());
@@ -201,7 +261,7 @@ fn brief_code(content: &str, lang: &str, theme: &Theme, out: &Output) {
 }
 
 fn matches_keyword(trimmed: &str, keywords: &[&str]) -> bool {
-    keywords.iter().any(|kw| trimmed.starts_with(kw))
+    crate::langprofile::matches_keyword(trimmed, keywords)
 }
 
 fn keywords_for(lang: &str) -> &'static [&'static str] {
@@ -294,21 +354,7 @@ fn keywords_for(lang: &str) -> &'static [&'static str] {
 // ─── C/C++ function definition heuristic ───
 // Matches lines like `int main(` or `void* foo(` but not `if (`, `while (`, etc.
 fn is_c_func_def(line: &str) -> bool {
-    if line.starts_with(' ') || line.starts_with('\t') {
-        return false;
-    }
-    if !line.contains('(') {
-        return false;
-    }
-    let control = ["if ", "if(", "else ", "while ", "while(", "for ", "for(",
-                    "switch ", "switch(", "return ", "return(", "//", "/*", "#"];
-    if control.iter().any(|kw| line.starts_with(kw)) {
-        return false;
-    }
-    let paren_pos = line.find('(').unwrap();
-    let before = line[..paren_pos].trim();
-    // Must have at least two tokens (return type + name) before `(`
-    before.contains(' ') || before.contains('*')
+    crate::langprofile::is_c_func_def(line)
 }
 
 // ─── CSS selector heuristic ───
@@ -332,15 +378,7 @@ fn brief_css(content: &str, theme: &Theme, out: &Output) {
 }
 
 fn is_css_selector(line: &str) -> bool {
-    let trimmed = line.trim();
-    if trimmed.is_empty() || trimmed.starts_with("/*") || trimmed.starts_with("//") {
-        return false;
-    }
-    // @import, @media, @keyframes, etc.
-    if trimmed.starts_with('@') {
-        return true;
-    }
-    trimmed.ends_with('{')
+    crate::langprofile::is_css_selector(line)
 }
 
 // ─── Batch File ───
@@ -385,41 +423,119 @@ fn brief_asm(content: &str, theme: &Theme, out: &Output) {
 }
 
 fn is_asm_structural(line: &str) -> bool {
-    let trimmed = line.trim();
-    if trimmed.is_empty() || trimmed.starts_with(';') {
-        return false;
-    }
-    // Labels: `word:`
-    if trimmed.contains(':') && !trimmed.starts_with('.') {
-        let colon_pos = trimmed.find(':').unwrap();
-        let before = &trimmed[..colon_pos];
-        if !before.is_empty() && before.chars().all(|c| c.is_alphanumeric() || c == '_') {
-            return true;
-        }
-    }
-    let lower = trimmed.to_lowercase();
-    lower.starts_with("section ") || lower.starts_with("global ") || lower.starts_with(".section ")
+    crate::langprofile::is_asm_label(line)
 }
 
 // ─── Haskell type signature ───
 
 fn has_haskell_sig(line: &str) -> bool {
-    if line.starts_with(' ') || line.starts_with('\t') || line.starts_with("--") {
-        return false;
-    }
-    line.contains(" :: ")
+    crate::langprofile::has_haskell_sig(line)
 }
 
 // ─── Shell function pattern: `name() {` ───
 
 fn is_shell_func(line: &str) -> bool {
-    let trimmed = line.trim();
-    trimmed.ends_with("() {") || trimmed.ends_with("(){")
+    crate::langprofile::is_shell_func(line)
 }
 
 // ─── YAML ───
 
+/// Parses every document in the stream with `serde_yaml` and summarizes its
+/// top-level and second-level keys the same way `brief_json_value` does for
+/// JSON. Falls back to the old indent/colon line scan when a document fails
+/// to parse (e.g. a genuinely broken file, or a construct `serde_yaml`
+/// rejects), since that scan needs no structured model to produce output.
 fn brief_yaml(content: &str, theme: &Theme, out: &Output) {
+    let documents: Vec<Result<serde_yaml::Value, serde_yaml::Error>> =
+        serde_yaml::Deserializer::from_str(content)
+            .map(serde_yaml::Value::deserialize)
+            .collect();
+
+    if documents.is_empty() || documents.iter().any(|d| d.is_err()) {
+        return brief_yaml_line_scan(content, theme, out);
+    }
+
+    let multi = documents.len() > 1;
+    for (i, doc) in documents.iter().enumerate() {
+        let value = doc.as_ref().expect("checked above");
+        if multi {
+            out.dim(&format!("  --- document {} ---\n", i + 1), theme.line_number);
+        }
+        brief_yaml_value(value, theme, out);
+    }
+}
+
+fn brief_yaml_value(val: &serde_yaml::Value, theme: &Theme, out: &Output) {
+    match val {
+        serde_yaml::Value::Mapping(map) => {
+            for (key, value) in map {
+                let summary = yaml_type_summary(value);
+                out.colored("  ", theme.text);
+                out.bold_colored(&yaml_key_to_string(key), theme.json_key);
+                out.dim(&format!(": {}\n", summary), theme.line_number);
+            }
+        }
+        serde_yaml::Value::Sequence(seq) => {
+            let summary = if seq.is_empty() {
+                "[] (empty)".to_string()
+            } else {
+                let first_type = yaml_type_name(&seq[0]);
+                format!("[{}] ({} items, {})", first_type, seq.len(), first_type)
+            };
+            out.dim(&format!("  {}\n", summary), theme.line_number);
+        }
+        _ => {
+            out.dim(&format!("  {}\n", yaml_type_name(val)), theme.line_number);
+        }
+    }
+}
+
+fn yaml_type_summary(val: &serde_yaml::Value) -> String {
+    match val {
+        serde_yaml::Value::Mapping(map) => format!("{{}} ({} keys)", map.len()),
+        serde_yaml::Value::Sequence(seq) => {
+            if seq.is_empty() {
+                "[] (empty)".to_string()
+            } else {
+                format!("[{}] ({} items)", yaml_type_name(&seq[0]), seq.len())
+            }
+        }
+        serde_yaml::Value::String(s) => {
+            if s.len() > 40 {
+                format!("\"{}...\"", truncate_at_char_boundary(s, 37))
+            } else {
+                format!("\"{}\"", s)
+            }
+        }
+        serde_yaml::Value::Number(n) => n.to_string(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        serde_yaml::Value::Null => "null".to_string(),
+        serde_yaml::Value::Tagged(t) => format!("!{} {}", t.tag, yaml_type_summary(&t.value)),
+    }
+}
+
+fn yaml_type_name(val: &serde_yaml::Value) -> &'static str {
+    match val {
+        serde_yaml::Value::Mapping(_) => "mapping",
+        serde_yaml::Value::Sequence(_) => "sequence",
+        serde_yaml::Value::String(_) => "string",
+        serde_yaml::Value::Number(_) => "number",
+        serde_yaml::Value::Bool(_) => "bool",
+        serde_yaml::Value::Null => "null",
+        serde_yaml::Value::Tagged(_) => "tagged",
+    }
+}
+
+fn yaml_key_to_string(key: &serde_yaml::Value) -> String {
+    match key {
+        serde_yaml::Value::String(s) => s.clone(),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        other => yaml_type_summary(other),
+    }
+}
+
+fn brief_yaml_line_scan(content: &str, theme: &Theme, out: &Output) {
     let lines: Vec<&str> = content.lines().collect();
     let width = line_num_width(lines.len());
     let mut found = false;
@@ -489,6 +605,48 @@ fn brief_html(content: &str, theme: &Theme, out: &Output) {
     }
 }
 
+// ─── Org-mode ───
+
+fn brief_org(content: &str, theme: &Theme, out: &Output) {
+    let lines: Vec<&str> = content.lines().collect();
+    let width = line_num_width(lines.len());
+    let mut found = false;
+
+    for (i, line) in lines.iter().enumerate() {
+        if is_org_structural(line.trim_start()) {
+            print_line(i + 1, width, line, theme, out);
+            found = true;
+        }
+    }
+
+    if !found {
+        out.dim("  (no brief outline for Org)\n", theme.line_number);
+    }
+}
+
+/// True for Org document keywords (`#+TITLE:`, `#+AUTHOR:`, `#+PROPERTY:`),
+/// headlines (one or more `*` followed by a space), and `#+BEGIN_SRC` /
+/// `#+END_SRC` block markers.
+fn is_org_structural(trimmed: &str) -> bool {
+    if is_org_keyword(trimmed) {
+        return true;
+    }
+
+    let stars = trimmed.chars().take_while(|&c| c == '*').count();
+    if stars > 0 && trimmed.as_bytes().get(stars) == Some(&b' ') {
+        return true;
+    }
+
+    let lower = trimmed.to_lowercase();
+    lower.starts_with("#+begin_src") || lower.starts_with("#+end_src")
+}
+
+fn is_org_keyword(trimmed: &str) -> bool {
+    ["#+TITLE:", "#+AUTHOR:", "#+PROPERTY:"]
+        .iter()
+        .any(|kw| trimmed.len() >= kw.len() && trimmed[..kw.len()].eq_ignore_ascii_case(kw))
+}
+
 // ─── Plain text fallback ───
 
 fn brief_plain(content: &str, theme: &Theme, out: &Output) {
@@ -496,6 +654,401 @@ fn brief_plain(content: &str, theme: &Theme, out: &Output) {
     out.dim(&format!("  {} lines (plain text — no structure to outline)\n", line_count), theme.line_number);
 }
 
+// ─── Hierarchical outline (nested by scope / indentation) ───
+
+/// A single entry in the nested document-symbol tree produced by
+/// `structural_tree`. Mirrors an editor's document-symbol pane: an `impl`
+/// block or `class` contains its methods as `children`, a Markdown `#`
+/// heading contains the `##` headings beneath it, and so on.
+pub struct OutlineNode<'a> {
+    pub line: usize,
+    pub text: &'a str,
+    pub kind: &'static str,
+    pub children: Vec<OutlineNode<'a>>,
+}
+
+/// Builds a nested outline tree for `content`, or an empty vec for formats
+/// with nothing to nest (JSON/CSV/Plain use their own dedicated renderers).
+pub fn structural_tree<'a>(content: &'a str, format: &FileFormat) -> Vec<OutlineNode<'a>> {
+    match format {
+        FileFormat::Markdown => markdown_tree(content),
+        FileFormat::Code(lang) => {
+            let normalized = lang.to_lowercase();
+            match normalized.as_str() {
+                "yaml" | "yml" => indent_tree(content, 2),
+                "toml" => toml_tree(content),
+                "python" => indent_tree(content, 4),
+                _ => brace_scope_tree(content, &normalized),
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn markdown_tree(content: &str) -> Vec<OutlineNode<'_>> {
+    let mut roots: Vec<OutlineNode> = Vec::new();
+    // Stack of (level, node) for headings still open for nesting.
+    let mut stack: Vec<(u8, OutlineNode)> = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if !trimmed.starts_with('#') {
+            continue;
+        }
+        let level = trimmed.chars().take_while(|&c| c == '#').count() as u8;
+        if level == 0 || level > 6 {
+            continue;
+        }
+        let node = OutlineNode {
+            line: i + 1,
+            text: line,
+            kind: "§",
+            children: Vec::new(),
+        };
+        push_by_level(&mut roots, &mut stack, level, node);
+    }
+    flush_stack(&mut roots, &mut stack);
+    roots
+}
+
+/// Pushes `node` under the deepest open heading/section shallower than
+/// `level`, popping anything on the stack that is not an ancestor.
+fn push_by_level<'a>(
+    roots: &mut Vec<OutlineNode<'a>>,
+    stack: &mut Vec<(u8, OutlineNode<'a>)>,
+    level: u8,
+    node: OutlineNode<'a>,
+) {
+    while let Some((top_level, _)) = stack.last() {
+        if *top_level >= level {
+            let (_, finished) = stack.pop().unwrap();
+            attach(roots, stack, finished);
+        } else {
+            break;
+        }
+    }
+    stack.push((level, node));
+}
+
+fn attach<'a>(
+    roots: &mut Vec<OutlineNode<'a>>,
+    stack: &mut [(u8, OutlineNode<'a>)],
+    node: OutlineNode<'a>,
+) {
+    if let Some((_, parent)) = stack.last_mut() {
+        parent.children.push(node);
+    } else {
+        roots.push(node);
+    }
+}
+
+fn flush_stack<'a>(roots: &mut Vec<OutlineNode<'a>>, stack: &mut Vec<(u8, OutlineNode<'a>)>) {
+    while let Some((_, node)) = stack.pop() {
+        attach(roots, stack, node);
+    }
+}
+
+/// TOML section nesting: `[a.b.c]` becomes a child of `[a.b]`, which is a
+/// child of `[a]`, matched purely on the dotted-name prefix.
+fn toml_tree(content: &str) -> Vec<OutlineNode<'_>> {
+    let mut roots: Vec<OutlineNode> = Vec::new();
+    let mut path_stack: Vec<(String, usize)> = Vec::new(); // (dotted name, index into tree path)
+
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if !trimmed.starts_with('[') {
+            continue;
+        }
+        let inner = trimmed
+            .trim_start_matches('[')
+            .trim_start_matches('[')
+            .split(']')
+            .next()
+            .unwrap_or("")
+            .trim();
+        let node = OutlineNode {
+            line: i + 1,
+            text: line,
+            kind: "[]",
+            children: Vec::new(),
+        };
+
+        let depth = inner.matches('.').count() + 1;
+        while path_stack.len() >= depth {
+            path_stack.pop();
+        }
+        path_stack.push((inner.to_string(), depth));
+
+        insert_at_depth(&mut roots, depth - 1, node);
+    }
+    roots
+}
+
+fn insert_at_depth<'a>(roots: &mut Vec<OutlineNode<'a>>, depth: usize, node: OutlineNode<'a>) {
+    if depth == 0 || roots.is_empty() {
+        roots.push(node);
+        return;
+    }
+    if let Some(last) = roots.last_mut() {
+        insert_at_depth(&mut last.children, depth - 1, node);
+    } else {
+        roots.push(node);
+    }
+}
+
+/// Indentation-based nesting for languages where a child's leading
+/// whitespace strictly increases (Python, YAML): each `unit` spaces count
+/// as one nesting level (tabs count as one `unit`).
+fn indent_tree(content: &str, unit: usize) -> Vec<OutlineNode<'_>> {
+    let mut roots: Vec<OutlineNode> = Vec::new();
+    let mut stack: Vec<(usize, OutlineNode)> = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let normalized = line.to_lowercase();
+        let is_def = normalized.trim_start().starts_with("def ")
+            || normalized.trim_start().starts_with("class ")
+            || normalized.trim_start().starts_with("async def ")
+            || (line.contains(':') && line.len() - line.trim_start().len() <= 2);
+        if !is_def {
+            continue;
+        }
+        let leading = &line[..line.len() - line.trim_start().len()];
+        let width: usize = leading.chars().map(|c| if c == '\t' { unit.max(1) } else { 1 }).sum();
+        let indent = width / unit.max(1);
+        let node = OutlineNode {
+            line: i + 1,
+            text: line,
+            kind: "·",
+            children: Vec::new(),
+        };
+
+        while let Some(&(top_indent, _)) = stack.last() {
+            if top_indent >= indent {
+                let (_, finished) = stack.pop().unwrap();
+                attach_indent(&mut roots, &mut stack, finished);
+            } else {
+                break;
+            }
+        }
+        stack.push((indent, node));
+    }
+    while let Some((_, node)) = stack.pop() {
+        attach_indent(&mut roots, &mut stack, node);
+    }
+    roots
+}
+
+fn attach_indent<'a>(
+    roots: &mut Vec<OutlineNode<'a>>,
+    stack: &mut [(usize, OutlineNode<'a>)],
+    node: OutlineNode<'a>,
+) {
+    if let Some((_, parent)) = stack.last_mut() {
+        parent.children.push(node);
+    } else {
+        roots.push(node);
+    }
+}
+
+/// Brace-scope nesting for C-family/Rust-like languages: a `struct`/`impl`/
+/// `class` keyword line opens a scope that lasts until its matching `}` and
+/// any structural line found inside becomes a child.
+fn brace_scope_tree<'a>(content: &'a str, lang: &str) -> Vec<OutlineNode<'a>> {
+    let keywords = keywords_for(lang);
+    let scope_openers = ["impl ", "class ", "struct ", "trait ", "namespace ", "mod "];
+    let mut roots: Vec<OutlineNode> = Vec::new();
+    let mut stack: Vec<(i32, OutlineNode)> = Vec::new(); // (brace depth at open, node)
+    let mut depth: i32 = 0;
+
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let is_structural = matches_keyword(trimmed, keywords);
+        let is_scope = scope_openers.iter().any(|kw| trimmed.starts_with(kw));
+
+        if is_structural {
+            let node = OutlineNode {
+                line: i + 1,
+                text: line,
+                kind: "fn",
+                children: Vec::new(),
+            };
+            if is_scope {
+                stack.push((depth, OutlineNode { kind: "{}", ..node }));
+            } else if let Some((_, parent)) = stack.last_mut() {
+                parent.children.push(node);
+            } else {
+                roots.push(node);
+            }
+        }
+
+        depth += line.matches('{').count() as i32 - line.matches('}').count() as i32;
+
+        while let Some(&(open_depth, _)) = stack.last() {
+            if depth <= open_depth && line.contains('}') {
+                let (_, finished) = stack.pop().unwrap();
+                if let Some((_, parent)) = stack.last_mut() {
+                    parent.children.push(finished);
+                } else {
+                    roots.push(finished);
+                }
+            } else {
+                break;
+            }
+        }
+    }
+    while let Some((_, node)) = stack.pop() {
+        if let Some((_, parent)) = stack.last_mut() {
+            parent.children.push(node);
+        } else {
+            roots.push(node);
+        }
+    }
+    roots
+}
+
+/// Renders a nested outline, indenting `"  "` per depth and prefixing each
+/// entry with its kind glyph.
+pub fn render_tree(nodes: &[OutlineNode], depth: usize, width: usize, theme: &Theme, out: &Output) {
+    for node in nodes {
+        out.dim(&format!(" {:>w$} │ ", node.line, w = width), theme.line_number);
+        out.raw(&"  ".repeat(depth));
+        out.dim(&format!("{} ", node.kind), theme.line_number);
+        out.colored(node.text.trim_start(), theme.text);
+        out.newline();
+        render_tree(&node.children, depth + 1, width, theme, out);
+    }
+}
+
+// ─── Machine-readable outline (LSP documentSymbol shape) ───
+
+/// Serializes the extracted outline as an LSP-style array of `documentSymbol`
+/// objects to stdout. Reuses `structural_tree`/`crate::symbols::extract_symbols`
+/// — the same extraction that feeds the text renderer — so the two views can
+/// never diverge. Writes plain UTF-8 JSON; no theme/color calls.
+pub fn render_json(content: &str, format: &FileFormat, out: &Output) {
+    let symbols = document_symbols(content, format);
+    if let Ok(text) = serde_json::to_string_pretty(&symbols) {
+        out.raw(&text);
+        out.newline();
+    }
+}
+
+fn document_symbols(content: &str, format: &FileFormat) -> Vec<serde_json::Value> {
+    if let FileFormat::Code(lang) = format {
+        let normalized = lang.to_lowercase();
+        if let Some(symbols) = crate::symbols::extract_symbols(content, &normalized) {
+            return symbols.iter().map(symbol_to_json).collect();
+        }
+    }
+    structural_tree(content, format)
+        .iter()
+        .map(node_to_json)
+        .collect()
+}
+
+fn symbol_to_json(sym: &crate::symbols::Symbol) -> serde_json::Value {
+    serde_json::json!({
+        "name": sym.name,
+        "kind": symbol_kind_name(sym.kind),
+        "line": sym.line,
+        "endLine": sym.end_line,
+        "detail": sym.signature,
+        "children": [],
+    })
+}
+
+fn symbol_kind_name(kind: crate::symbols::SymbolKind) -> &'static str {
+    use crate::symbols::SymbolKind::*;
+    match kind {
+        Function | Method => "Function",
+        Class => "Class",
+        Struct => "Struct",
+        Enum => "Enum",
+        Trait | Interface => "Interface",
+        Module => "Module",
+        Field => "Field",
+    }
+}
+
+fn node_to_json(node: &OutlineNode) -> serde_json::Value {
+    let text = node.text.trim_start();
+    serde_json::json!({
+        "name": node_symbol_name(node.kind, text),
+        "kind": node_kind_name(node.kind, text),
+        "line": node.line,
+        "endLine": node.line,
+        "detail": text,
+        "children": node.children.iter().map(node_to_json).collect::<Vec<_>>(),
+    })
+}
+
+fn node_kind_name(glyph: &str, text: &str) -> &'static str {
+    match glyph {
+        "§" => "Heading",
+        "[]" => "Section",
+        "·" => {
+            if text.starts_with("class ") {
+                "Class"
+            } else {
+                "Function"
+            }
+        }
+        "{}" => {
+            if text.starts_with("struct ") {
+                "Struct"
+            } else if text.starts_with("enum ") {
+                "Enum"
+            } else if text.starts_with("trait ") || text.starts_with("interface ") {
+                "Interface"
+            } else if text.starts_with("class ") {
+                "Class"
+            } else if text.starts_with("mod ") || text.starts_with("namespace ") {
+                "Module"
+            } else {
+                "Section"
+            }
+        }
+        _ => "Function",
+    }
+}
+
+fn node_symbol_name(glyph: &str, text: &str) -> String {
+    match glyph {
+        "§" => text.trim_start_matches('#').trim().to_string(),
+        "[]" => text.trim_matches(|c| c == '[' || c == ']').trim().to_string(),
+        _ => extract_identifier(text),
+    }
+}
+
+/// Picks the declared name out of a structural line like `pub fn foo(x: i32)`
+/// or `struct Bar {` by looking for the token right after a declaration
+/// keyword. Falls back to the trimmed line when no keyword is recognized.
+fn extract_identifier(text: &str) -> String {
+    let cleaned = text.trim_end_matches(['{', ':']).trim();
+    let tokens: Vec<&str> = cleaned.split_whitespace().collect();
+    const KEYWORDS: &[&str] = &[
+        "fn", "struct", "enum", "trait", "class", "interface", "mod", "namespace",
+        "impl", "def", "func", "function", "module",
+    ];
+    for (i, tok) in tokens.iter().enumerate() {
+        if KEYWORDS.contains(tok) {
+            if let Some(next) = tokens.get(i + 1) {
+                let name: String = next
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric() || *c == '_')
+                    .collect();
+                if !name.is_empty() {
+                    return name;
+                }
+            }
+        }
+    }
+    cleaned.to_string()
+}
+
 // ─── Structural line extraction (for -b + -g combo) ───
 
 /// Returns (1-based line number, line text) for lines considered structural.
@@ -509,27 +1062,29 @@ pub fn structural_lines<'a>(content: &'a str, format: &FileFormat) -> Vec<(usize
             .filter(|(_, l)| l.trim_start().starts_with('#'))
             .map(|(i, l)| (i + 1, *l))
             .collect(),
-        FileFormat::Toml => lines
-            .iter()
-            .enumerate()
-            .filter(|(_, l)| l.trim().starts_with('['))
-            .map(|(i, l)| (i + 1, *l))
-            .collect(),
-        FileFormat::Yaml => lines
+        FileFormat::Code(lang) => collect_code_structural(&lines, lang),
+        FileFormat::Org => lines
             .iter()
             .enumerate()
-            .filter(|(_, l)| !l.is_empty() && l.len() - l.trim_start().len() <= 2 && l.contains(':'))
+            .filter(|(_, l)| is_org_structural(l.trim_start()))
             .map(|(i, l)| (i + 1, *l))
             .collect(),
-        FileFormat::Code(lang) => collect_code_structural(&lines, lang),
         _ => Vec::new(),
     }
 }
 
 fn collect_code_structural<'a>(lines: &[&'a str], lang: &str) -> Vec<(usize, &'a str)> {
     let normalized = lang.to_lowercase();
-    let mut result = Vec::new();
+    let content = lines.join("\n");
 
+    if let Some(symbols) = crate::symbols::extract_symbols(&content, &normalized) {
+        return symbols
+            .iter()
+            .filter_map(|s| lines.get(s.line - 1).map(|l| (s.line, *l)))
+            .collect();
+    }
+
+    let mut result = Vec::new();
     for (i, line) in lines.iter().enumerate() {
         if is_structural_code_line(line, &normalized) {
             result.push((i + 1, *line));
@@ -538,41 +1093,11 @@ fn collect_code_structural<'a>(lines: &[&'a str], lang: &str) -> Vec<(usize, &'a
     result
 }
 
+/// Delegates to `langprofile`'s `LanguageProfile`/`Matcher` registry — see
+/// that module for the per-language keyword/heuristic table this used to
+/// duplicate inline.
 fn is_structural_code_line(line: &str, lang: &str) -> bool {
-    let trimmed = line.trim_start();
-
-    match lang {
-        "yaml" | "yml" => {
-            !line.is_empty() && line.len() - line.trim_start().len() <= 2 && line.contains(':')
-        }
-        "toml" => trimmed.starts_with('['),
-        "html" | "html (rails)" | "html (tcl)" => {
-            let lower = trimmed.to_lowercase();
-            lower.starts_with("<title")
-                || lower.starts_with("<h1")
-                || lower.starts_with("<h2")
-                || lower.starts_with("<h3")
-                || lower.starts_with("<h4")
-                || lower.starts_with("<h5")
-                || lower.starts_with("<h6")
-        }
-        "css" | "scss" | "sass" | "less" => is_css_selector(trimmed),
-        "batch file" | "bat" | "cmd" => trimmed.starts_with(':') && !trimmed.starts_with("::"),
-        "asm" | "nasm" | "assembly" => is_asm_structural(trimmed),
-        _ => {
-            let keywords = keywords_for(lang);
-            if matches_keyword(trimmed, keywords) {
-                return true;
-            }
-            match lang {
-                "c" | "c++" | "objective-c" | "objective-c++" => is_c_func_def(line),
-                "haskell" => has_haskell_sig(line),
-                "bash" | "sh" | "zsh" | "fish" | "shell"
-                | "bourne again shell (bash)" => is_shell_func(line),
-                _ => false,
-            }
-        }
-    }
+    crate::langprofile::is_structural_line(line, lang)
 }
 
 // ─── Output helpers ───
@@ -580,7 +1105,14 @@ fn is_structural_code_line(line: &str, lang: &str) -> bool {
 fn print_line(num: usize, width: usize, text: &str, theme: &Theme, out: &Output) {
     out.dim(&format!(" {:>w$} │ ", num, w = width), theme.line_number);
     out.colored(text, theme.text);
-    println!();
+    out.newline();
+}
+
+fn print_symbol(sym: &crate::symbols::Symbol, width: usize, theme: &Theme, out: &Output) {
+    out.dim(&format!(" {:>w$} │ ", sym.line, w = width), theme.line_number);
+    out.dim(&format!("{} ", sym.kind.glyph()), theme.line_number);
+    out.colored(&sym.signature, theme.text);
+    out.newline();
 }
 
 fn line_num_width(total: usize) -> usize {
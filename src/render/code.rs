@@ -1,34 +1,16 @@
 use crossterm::style::Color;
 use syntect::easy::HighlightLines;
-use syntect::highlighting::{FontStyle, Style, ThemeSet};
+use syntect::highlighting::{FontStyle, Style};
 use syntect::parsing::SyntaxSet;
 use syntect::util::LinesWithEndings;
 
 use crate::output::Output;
+use crate::render::syntax::{find_syntax, theme_set};
 use crate::theme::Theme;
 
 pub fn render(content: &str, lang: &str, line_numbers: bool, theme: &Theme, out: &Output) {
-    let ss = SyntaxSet::load_defaults_newlines();
-    let ts = ThemeSet::load_defaults();
-
-    let syntax = ss
-        .find_syntax_by_name(lang)
-        .or_else(|| ss.find_syntax_by_extension(&lang.to_lowercase()))
-        .or_else(|| ss.find_syntax_by_token(&lang.to_lowercase()))
-        .or_else(|| {
-            let fb = crate::detect::syntax_fallback(lang);
-            ss.find_syntax_by_name(fb)
-                .or_else(|| ss.find_syntax_by_token(fb))
-        })
-        .unwrap_or_else(|| ss.find_syntax_plain_text());
-
-    let highlight_theme = ts
-        .themes
-        .get(theme.syntect_theme)
-        .or_else(|| ts.themes.get("Monokai Extended"))
-        .unwrap_or_else(|| ts.themes.values().next().unwrap());
-
-    let mut h = HighlightLines::new(syntax, highlight_theme);
+    let (ss, syntax) = find_syntax(lang);
+    let mut h = HighlightLines::new(syntax, highlight_theme(theme));
 
     let lines: Vec<&str> = LinesWithEndings::from(content).collect();
     let line_count = lines.len();
@@ -42,27 +24,73 @@ pub fn render(content: &str, lang: &str, line_numbers: bool, theme: &Theme, out:
         if line_numbers {
             out.dim(&format!(" {:>width$} │ ", i + 1, width = num_width), theme.line_number);
         }
-
-        match h.highlight_line(line, &ss) {
-            Ok(ranges) => {
-                for (style, text) in ranges {
-                    let color = syntect_to_crossterm(style);
-                    if style.font_style.contains(FontStyle::BOLD) {
-                        out.bold_colored(text, color);
-                    } else if style.font_style.contains(FontStyle::ITALIC) {
-                        out.italic_colored(text, color);
-                    } else {
-                        out.colored(text, color);
-                    }
-                }
-            }
-            Err(_) => print!("{}", line),
-        }
+        write_highlighted(&mut h, ss, line, false, out);
     }
 
     // Ensure final newline
     if !content.ends_with('\n') {
-        println!();
+        out.newline();
+    }
+}
+
+/// Incremental per-line driver over syntect, for callers that hand a code
+/// block's lines over one at a time interleaved with other content (e.g.
+/// Org's `#+BEGIN_SRC` blocks) rather than all at once the way `render`
+/// does. Keeping one `HighlightLines` alive across calls is what lets
+/// multi-line constructs (block comments, strings) still highlight
+/// correctly even though each line arrives in its own call.
+pub struct LineHighlighter {
+    h: HighlightLines<'static>,
+    ss: &'static SyntaxSet,
+}
+
+impl LineHighlighter {
+    pub fn new(lang: &str, theme: &Theme) -> Self {
+        let (ss, syntax) = find_syntax(lang);
+        Self { h: HighlightLines::new(syntax, highlight_theme(theme)), ss }
+    }
+
+    /// Renders one line, without its trailing newline, through the
+    /// highlighter's running state — the caller is expected to end the
+    /// line itself, the way Org's line-at-a-time renderer does.
+    pub fn render_line(&mut self, line: &str, out: &Output) {
+        let with_newline = format!("{}\n", line);
+        write_highlighted(&mut self.h, self.ss, &with_newline, true, out);
+    }
+}
+
+fn highlight_theme(theme: &Theme) -> &'static syntect::highlighting::Theme {
+    let ts = theme_set();
+    ts.themes
+        .get(theme.syntect_theme)
+        .or_else(|| ts.themes.get("Monokai Extended"))
+        .unwrap_or_else(|| ts.themes.values().next().unwrap())
+}
+
+/// Highlights one `line` (as returned by `LinesWithEndings`, i.e. still
+/// carrying its trailing `\n`) and writes it through `out`. `render` wants
+/// that `\n` kept, since it's what produces the line break across a whole
+/// document rendered in one call; `LineHighlighter::render_line` strips it,
+/// since its caller owns ending the line instead.
+fn write_highlighted(h: &mut HighlightLines<'_>, ss: &SyntaxSet, line: &str, strip_trailing_newline: bool, out: &Output) {
+    match h.highlight_line(line, ss) {
+        Ok(ranges) => {
+            for (style, text) in ranges {
+                let color = syntect_to_crossterm(style);
+                let text = if strip_trailing_newline { text.strip_suffix('\n').unwrap_or(text) } else { text };
+                if style.font_style.contains(FontStyle::BOLD) {
+                    out.bold_colored(text, color);
+                } else if style.font_style.contains(FontStyle::ITALIC) {
+                    out.italic_colored(text, color);
+                } else {
+                    out.colored(text, color);
+                }
+            }
+        }
+        Err(_) => {
+            let text = if strip_trailing_newline { line.strip_suffix('\n').unwrap_or(line) } else { line };
+            out.raw(text);
+        }
     }
 }
 
@@ -27,13 +27,13 @@ pub fn render(content: &str, theme: &Theme, out: &Output) {
     let rows = parse_csv(content, delimiter);
 
     if rows.is_empty() {
-        print!("{}", content);
+        out.raw(content);
         return;
     }
 
     let col_count = rows.iter().map(|r| r.len()).max().unwrap_or(0);
     if col_count == 0 {
-        print!("{}", content);
+        out.raw(content);
         return;
     }
 
@@ -58,7 +58,7 @@ pub fn render(content: &str, theme: &Theme, out: &Output) {
     print_border_top(&widths, col_count, border_color, out);
 
     for (r, row) in rows.iter().enumerate() {
-        print!("  ");
+        out.raw("  ");
         out.colored("│", border_color);
 
         for (c, w) in widths.iter().enumerate() {
@@ -68,19 +68,19 @@ pub fn render(content: &str, theme: &Theme, out: &Output) {
 
             let col_color = column_color(c);
 
-            print!(" ");
+            out.raw(" ");
             if r == 0 {
                 out.bold_colored(&truncated, col_color);
             } else {
                 out.colored(&truncated, col_color);
             }
             for _ in 0..padding {
-                print!(" ");
+                out.raw(" ");
             }
-            print!(" ");
+            out.raw(" ");
             out.colored("│", border_color);
         }
-        println!();
+        out.newline();
 
         if r == 0 && rows.len() > 1 {
             print_border_mid(&widths, col_count, border_color, out);
@@ -179,31 +179,31 @@ fn truncate_str(s: &str, max_len: usize) -> String {
 }
 
 fn print_border_top(widths: &[usize], col_count: usize, color: Color, out: &Output) {
-    print!("  ");
+    out.raw("  ");
     out.colored("┌", color);
     for (i, w) in widths.iter().enumerate() {
         out.colored(&"─".repeat(*w + 2), color);
         out.colored(if i < col_count - 1 { "┬" } else { "┐" }, color);
     }
-    println!();
+    out.newline();
 }
 
 fn print_border_mid(widths: &[usize], col_count: usize, color: Color, out: &Output) {
-    print!("  ");
+    out.raw("  ");
     out.colored("├", color);
     for (i, w) in widths.iter().enumerate() {
         out.colored(&"─".repeat(*w + 2), color);
         out.colored(if i < col_count - 1 { "┼" } else { "┤" }, color);
     }
-    println!();
+    out.newline();
 }
 
 fn print_border_bottom(widths: &[usize], col_count: usize, color: Color, out: &Output) {
-    print!("  ");
+    out.raw("  ");
     out.colored("└", color);
     for (i, w) in widths.iter().enumerate() {
         out.colored(&"─".repeat(*w + 2), color);
         out.colored(if i < col_count - 1 { "┴" } else { "┘" }, color);
     }
-    println!();
+    out.newline();
 }
@@ -0,0 +1,229 @@
+//! Git diff renderer — working tree vs HEAD with intra-line word highlighting.
+//!
+//! Runs `git diff HEAD` and parses the unified diff output. Context lines
+//! are syntax-highlighted like `render::blame`; removed/added lines are
+//! tinted whole-line, then paired positionally within a hunk so the tokens
+//! that actually changed between an old/new line pair get a brighter
+//! highlight via a word-level LCS, leaving unchanged tokens at the plain
+//! removed/added color.
+
+use std::path::Path;
+use std::process::Command;
+
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{FontStyle, Style};
+use syntect::parsing::SyntaxSet;
+
+use crate::output::Output;
+use crate::render::syntax::{find_syntax, theme_set};
+use crate::theme::Theme;
+
+pub fn render(path: &Path, lang: &str, theme: &Theme, out: &Output) {
+    let cmd = match Command::new("git").args(["diff", "HEAD", "--"]).arg(path).output() {
+        Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout).into_owned(),
+        Ok(o) => {
+            eprintln!("vita: git diff failed: {}", String::from_utf8_lossy(&o.stderr).trim());
+            return;
+        }
+        Err(e) => {
+            eprintln!("vita: failed to run git: {}", e);
+            return;
+        }
+    };
+
+    if cmd.trim().is_empty() {
+        return;
+    }
+
+    let ts = theme_set();
+    let (ss, syntax) = find_syntax(lang);
+    let highlight_theme = ts
+        .themes
+        .get(theme.syntect_theme)
+        .or_else(|| ts.themes.get("Monokai Extended"))
+        .unwrap_or_else(|| ts.themes.values().next().unwrap());
+    let mut h = HighlightLines::new(syntax, highlight_theme);
+
+    let mut removed: Vec<&str> = Vec::new();
+    let mut added: Vec<&str> = Vec::new();
+    let mut in_hunk = false;
+
+    for line in cmd.lines() {
+        if let Some(header) = line.strip_prefix("@@") {
+            flush_pair(&removed, &added, theme, out);
+            removed.clear();
+            added.clear();
+            in_hunk = true;
+            out.dim(&format!("  @@{}", header), theme.line_number);
+            out.newline();
+            continue;
+        }
+
+        if !in_hunk {
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("-") {
+            if !line.starts_with("---") {
+                removed.push(rest);
+                continue;
+            }
+        }
+
+        if let Some(rest) = line.strip_prefix("+") {
+            if !line.starts_with("+++") {
+                added.push(rest);
+                continue;
+            }
+        }
+
+        flush_pair(&removed, &added, theme, out);
+        removed.clear();
+        added.clear();
+
+        let context = line.strip_prefix(' ').unwrap_or(line);
+        out.dim("    ", theme.line_number);
+        render_highlighted_line(context, &mut h, ss, out);
+    }
+
+    flush_pair(&removed, &added, theme, out);
+}
+
+fn render_highlighted_line(line: &str, h: &mut HighlightLines, ss: &SyntaxSet, out: &Output) {
+    let code_line = format!("{}\n", line);
+    match h.highlight_line(&code_line, ss) {
+        Ok(ranges) => {
+            for (style, text) in ranges {
+                let color = syntect_to_crossterm(style);
+                if style.font_style.contains(FontStyle::BOLD) {
+                    out.bold_colored(text, color);
+                } else if style.font_style.contains(FontStyle::ITALIC) {
+                    out.italic_colored(text, color);
+                } else {
+                    out.colored(text, color);
+                }
+            }
+        }
+        Err(_) => out.raw(&format!("{}\n", line)),
+    }
+}
+
+/// Pairs `removed`/`added` lines positionally: overlapping pairs get a
+/// word-level diff, leftovers are shown as whole-line changes.
+fn flush_pair(removed: &[&str], added: &[&str], theme: &Theme, out: &Output) {
+    let pairs = removed.len().min(added.len());
+
+    for i in 0..pairs {
+        render_word_diff(removed[i], added[i], theme, out);
+    }
+    for line in &removed[pairs..] {
+        out.dim("  - ", theme.line_number);
+        out.colored(line, theme.alert_warning);
+        out.newline();
+    }
+    for line in &added[pairs..] {
+        out.dim("  + ", theme.line_number);
+        out.colored(line, theme.diff_added);
+        out.newline();
+    }
+}
+
+fn render_word_diff(old_line: &str, new_line: &str, theme: &Theme, out: &Output) {
+    let old_tokens = tokenize(old_line);
+    let new_tokens = tokenize(new_line);
+    let (old_common, new_common) = lcs_common_mask(&old_tokens, &new_tokens);
+
+    out.dim("  - ", theme.line_number);
+    for (i, token) in old_tokens.iter().enumerate() {
+        if old_common[i] {
+            out.colored(token, theme.alert_warning);
+        } else {
+            out.colored_bg(token, theme.alert_warning, theme.diff_word_bg);
+        }
+    }
+    out.newline();
+
+    out.dim("  + ", theme.line_number);
+    for (i, token) in new_tokens.iter().enumerate() {
+        if new_common[i] {
+            out.colored(token, theme.diff_added);
+        } else {
+            out.colored_bg(token, theme.diff_added, theme.diff_word_bg);
+        }
+    }
+    out.newline();
+}
+
+/// Splits `s` into words (runs of `\w`) and individual punctuation/whitespace
+/// characters, so e.g. `"foo.bar()"` becomes `["foo", ".", "bar", "(", ")"]`.
+fn tokenize(s: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut in_word = false;
+    for (i, c) in s.char_indices() {
+        let is_word_char = c.is_alphanumeric() || c == '_';
+        if is_word_char {
+            if !in_word {
+                if i > start {
+                    tokens.push(&s[start..i]);
+                }
+                start = i;
+                in_word = true;
+            }
+        } else {
+            if in_word {
+                tokens.push(&s[start..i]);
+                start = i;
+                in_word = false;
+            }
+            tokens.push(&s[i..i + c.len_utf8()]);
+            start = i + c.len_utf8();
+        }
+    }
+    if start < s.len() {
+        tokens.push(&s[start..]);
+    }
+    tokens
+}
+
+/// Runs the standard LCS DP over two token sequences, then backtracks to
+/// mark which tokens in each sequence sit on the common subsequence.
+fn lcs_common_mask(a: &[&str], b: &[&str]) -> (Vec<bool>, Vec<bool>) {
+    let (m, n) = (a.len(), b.len());
+    let mut dp = vec![vec![0usize; n + 1]; m + 1];
+    for i in 1..=m {
+        for j in 1..=n {
+            dp[i][j] = if a[i - 1] == b[j - 1] {
+                dp[i - 1][j - 1] + 1
+            } else {
+                dp[i - 1][j].max(dp[i][j - 1])
+            };
+        }
+    }
+
+    let mut a_common = vec![false; m];
+    let mut b_common = vec![false; n];
+    let (mut i, mut j) = (m, n);
+    while i > 0 && j > 0 {
+        if a[i - 1] == b[j - 1] {
+            a_common[i - 1] = true;
+            b_common[j - 1] = true;
+            i -= 1;
+            j -= 1;
+        } else if dp[i - 1][j] >= dp[i][j - 1] {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+
+    (a_common, b_common)
+}
+
+fn syntect_to_crossterm(style: Style) -> crossterm::style::Color {
+    crossterm::style::Color::Rgb {
+        r: style.foreground.r,
+        g: style.foreground.g,
+        b: style.foreground.b,
+    }
+}
@@ -0,0 +1,185 @@
+//! Structured token-stream output, as an alternative to writing ANSI
+//! escapes directly.
+//!
+//! `TokenKind` classifies a highlighted span; `Emitter` is the sink a
+//! renderer calls into — `AnsiEmitter` reproduces the crate's normal
+//! colored-terminal output, `JsonEmitter` instead writes one JSON record
+//! per token (`{"line", "col_start", "col_end", "kind", "text"}`) so
+//! editors or other tools can consume highlighting info without parsing
+//! terminal escapes back out.
+//!
+//! The `*Changed`/`*Added`/`*Removed` hex variants exist for
+//! `hex::render_diff`'s binary comparison view, `*Match` for
+//! `hex::render`'s search mode, and `Elision` for both — plain hex
+//! rendering never emits any of them.
+
+use crossterm::style::Color;
+
+use crate::output::Output;
+use crate::theme::Theme;
+
+/// The semantic class of a highlighted token, independent of any one
+/// format's renderer.
+pub enum TokenKind {
+    Key,
+    StringValue,
+    /// An unquoted scalar that isn't a bool/null/number — YAML's bare
+    /// strings render dimmer than quoted ones, the same distinction
+    /// `render_typed_value` always made.
+    BareString,
+    Number,
+    Bool,
+    Null,
+    Comment,
+    Bracket,
+    ListBullet,
+    DocumentMarker,
+    HexOffset,
+    HexByte,
+    HexAscii,
+    HexByteChanged,
+    HexByteAdded,
+    HexByteRemoved,
+    HexAsciiChanged,
+    HexAsciiAdded,
+    HexAsciiRemoved,
+    HexByteMatch,
+    HexAsciiMatch,
+    Elision,
+}
+
+impl TokenKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TokenKind::Key => "key",
+            TokenKind::StringValue => "string_value",
+            TokenKind::BareString => "bare_string",
+            TokenKind::Number => "number",
+            TokenKind::Bool => "bool",
+            TokenKind::Null => "null",
+            TokenKind::Comment => "comment",
+            TokenKind::Bracket => "bracket",
+            TokenKind::ListBullet => "list_bullet",
+            TokenKind::DocumentMarker => "document_marker",
+            TokenKind::HexOffset => "hex_offset",
+            TokenKind::HexByte => "hex_byte",
+            TokenKind::HexAscii => "hex_ascii",
+            TokenKind::HexByteChanged => "hex_byte_changed",
+            TokenKind::HexByteAdded => "hex_byte_added",
+            TokenKind::HexByteRemoved => "hex_byte_removed",
+            TokenKind::HexAsciiChanged => "hex_ascii_changed",
+            TokenKind::HexAsciiAdded => "hex_ascii_added",
+            TokenKind::HexAsciiRemoved => "hex_ascii_removed",
+            TokenKind::HexByteMatch => "hex_byte_match",
+            TokenKind::HexAsciiMatch => "hex_ascii_match",
+            TokenKind::Elision => "elision",
+        }
+    }
+}
+
+/// The position of a token within the line it belongs to.
+pub struct Span {
+    pub line: usize,
+    pub col_start: usize,
+    pub col_end: usize,
+}
+
+/// A sink for classified output tokens. A renderer calls `emit` for each
+/// meaningful span and `literal` for glue text (separators, padding) that
+/// carries no semantic class of its own — `JsonEmitter` drops `literal`
+/// text entirely since it isn't part of the structured data.
+pub trait Emitter {
+    fn emit(&mut self, kind: TokenKind, span: Span, text: &str);
+
+    /// Non-semantic text between tokens — box-drawing separators,
+    /// padding. No-op by default, since only `AnsiEmitter` needs it to
+    /// reproduce the crate's normal visual layout.
+    fn literal(&mut self, _text: &str) {}
+
+    /// Called once a line's tokens have all been emitted.
+    fn end_line(&mut self) {}
+}
+
+/// Reproduces the crate's normal colored-terminal output by mapping each
+/// `TokenKind` to a `Theme` color and writing through `Output`.
+pub struct AnsiEmitter<'a> {
+    theme: &'a Theme,
+    out: &'a Output,
+}
+
+impl<'a> AnsiEmitter<'a> {
+    pub fn new(theme: &'a Theme, out: &'a Output) -> Self {
+        Self { theme, out }
+    }
+
+    fn color(&self, kind: &TokenKind) -> Color {
+        match kind {
+            TokenKind::Key => self.theme.json_key,
+            TokenKind::StringValue => self.theme.json_string,
+            TokenKind::BareString => self.theme.text,
+            TokenKind::Number => self.theme.json_number,
+            TokenKind::Bool => self.theme.json_bool,
+            TokenKind::Null => self.theme.json_null,
+            TokenKind::Comment => self.theme.line_number,
+            TokenKind::Bracket => self.theme.json_bracket,
+            TokenKind::ListBullet => self.theme.list_bullet,
+            TokenKind::DocumentMarker => self.theme.hr,
+            TokenKind::HexOffset => self.theme.hex_offset,
+            TokenKind::HexByte => self.theme.hex_byte,
+            TokenKind::HexAscii => self.theme.hex_ascii,
+            TokenKind::HexByteChanged | TokenKind::HexAsciiChanged => self.theme.diff_changed,
+            TokenKind::HexByteAdded | TokenKind::HexAsciiAdded => self.theme.diff_added,
+            TokenKind::HexByteRemoved | TokenKind::HexAsciiRemoved => self.theme.diff_removed,
+            TokenKind::HexByteMatch | TokenKind::HexAsciiMatch => self.theme.r#match,
+            TokenKind::Elision => self.theme.line_number,
+        }
+    }
+}
+
+impl Emitter for AnsiEmitter<'_> {
+    fn emit(&mut self, kind: TokenKind, _span: Span, text: &str) {
+        match kind {
+            TokenKind::Comment | TokenKind::Elision => self.out.dim(text, self.color(&kind)),
+            // A null byte renders dim so a run of them doesn't visually
+            // dominate the hex panel, same as a zero-value `00`.
+            TokenKind::HexByte if text.trim_end() == "00" => {
+                self.out.dim(text, self.color(&kind));
+            }
+            _ => self.out.colored(text, self.color(&kind)),
+        }
+    }
+
+    fn literal(&mut self, text: &str) {
+        self.out.colored(text, self.theme.line_number);
+    }
+
+    fn end_line(&mut self) {
+        self.out.newline();
+    }
+}
+
+/// Writes one JSON record per token instead of ANSI escapes, so highlight
+/// info can be piped into an editor or another tool.
+pub struct JsonEmitter<'a> {
+    out: &'a Output,
+}
+
+impl<'a> JsonEmitter<'a> {
+    pub fn new(out: &'a Output) -> Self {
+        Self { out }
+    }
+}
+
+impl Emitter for JsonEmitter<'_> {
+    fn emit(&mut self, kind: TokenKind, span: Span, text: &str) {
+        let record = serde_json::json!({
+            "line": span.line,
+            "col_start": span.col_start,
+            "col_end": span.col_end,
+            "kind": kind.as_str(),
+            "text": text,
+        });
+        self.out.raw(&record.to_string());
+        self.out.newline();
+    }
+}
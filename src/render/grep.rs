@@ -1,29 +1,82 @@
+//! Regex-based grep rendering shared by `--grep` and `--brief --grep`.
+//!
+//! Pattern compilation (smart-case) lives here so both callers match
+//! identically; `render` additionally expands matches to `-A`/`--before`/`-C`
+//! context lines, which brief mode has no use for since it already only
+//! shows structural lines.
+
+use regex::{Regex, RegexBuilder};
+use std::collections::BTreeSet;
+
 use crate::output::Output;
 use crate::theme::Theme;
 
-pub fn render(content: &str, pattern: &str, theme: &Theme, out: &Output) {
+/// How a pattern's case sensitivity is decided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseMode {
+    /// Case-insensitive unless the pattern contains an uppercase letter.
+    Smart,
+    /// Always case-sensitive, regardless of the pattern's casing.
+    Sensitive,
+}
+
+/// Compiles `pattern` into a `Regex`, resolving case sensitivity per `mode`.
+pub fn compile(pattern: &str, mode: CaseMode) -> Result<Regex, regex::Error> {
+    let case_insensitive = match mode {
+        CaseMode::Sensitive => false,
+        CaseMode::Smart => !pattern.chars().any(|c| c.is_uppercase()),
+    };
+    RegexBuilder::new(pattern)
+        .case_insensitive(case_insensitive)
+        .build()
+}
+
+pub fn render(content: &str, re: &Regex, before: usize, after: usize, theme: &Theme, out: &Output) {
     let lines: Vec<&str> = content.lines().collect();
-    let line_count = lines.len();
-    let num_width = format!("{}", line_count).len();
+    let num_width = format!("{}", lines.len()).len();
 
+    let mut shown: BTreeSet<usize> = BTreeSet::new();
     for (i, line) in lines.iter().enumerate() {
-        if !line.contains(pattern) {
-            continue;
+        if re.is_match(line) {
+            let start = i.saturating_sub(before);
+            let end = (i + after).min(lines.len().saturating_sub(1));
+            for j in start..=end {
+                shown.insert(j);
+            }
         }
+    }
 
-        out.dim(&format!(" {:>width$} │ ", i + 1, width = num_width), theme.line_number);
-
-        let mut rest = *line;
-        while let Some(pos) = rest.find(pattern) {
-            if pos > 0 {
-                out.colored(&rest[..pos], theme.text);
+    let mut prev: Option<usize> = None;
+    for &i in &shown {
+        if let Some(p) = prev {
+            if i > p + 1 {
+                out.dim("--", theme.line_number);
+                out.newline();
             }
-            out.colored_bg(pattern, theme.grep_match_fg, theme.grep_match_bg);
-            rest = &rest[pos + pattern.len()..];
         }
-        if !rest.is_empty() {
-            out.colored(rest, theme.text);
+        prev = Some(i);
+
+        out.dim(&format!(" {:>width$} │ ", i + 1, width = num_width), theme.line_number);
+        if re.is_match(lines[i]) {
+            highlight_matches(lines[i], re, theme, out);
+        } else {
+            out.dim(lines[i], theme.line_number);
+        }
+        out.newline();
+    }
+}
+
+/// Colors every match span on `line`, leaving the rest themed as plain text.
+pub fn highlight_matches(line: &str, re: &Regex, theme: &Theme, out: &Output) {
+    let mut last = 0;
+    for m in re.find_iter(line) {
+        if m.start() > last {
+            out.colored(&line[last..m.start()], theme.text);
         }
-        println!();
+        out.colored_bg(m.as_str(), theme.grep_match_fg, theme.grep_match_bg);
+        last = m.end();
+    }
+    if last < line.len() {
+        out.colored(&line[last..], theme.text);
     }
 }
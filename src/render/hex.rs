@@ -1,9 +1,109 @@
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use memchr::memchr;
+
 use crate::output::Output;
+use crate::render::emitter::{AnsiEmitter, Emitter, Span, TokenKind};
 use crate::theme::Theme;
 
 const BYTES_PER_LINE: usize = 16;
 
-pub fn render(data: &[u8], head: Option<usize>, tail: Option<usize>, theme: &Theme, out: &Output) {
+/// What `render`'s search mode looks for: either a literal byte sequence
+/// (from a hex pattern like `"de ad be ef"`) or a UTF-8 string matched
+/// against `data`'s raw bytes.
+pub enum Needle {
+    Bytes(Vec<u8>),
+    Text(String),
+}
+
+impl Needle {
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            Needle::Bytes(b) => b,
+            Needle::Text(s) => s.as_bytes(),
+        }
+    }
+}
+
+/// Parses a hex-byte pattern like `"de ad be ef"` into its raw bytes.
+/// Returns `None` if any whitespace-separated token isn't a valid
+/// two-digit hex byte.
+pub fn parse_hex_pattern(pattern: &str) -> Option<Vec<u8>> {
+    pattern
+        .split_whitespace()
+        .map(|tok| {
+            if tok.len() == 2 && tok.bytes().all(|b| b.is_ascii_hexdigit()) {
+                u8::from_str_radix(tok, 16).ok()
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Search specification for `render`/`render_with_emitter`'s highlight
+/// and grep modes.
+pub struct SearchSpec {
+    pub needle: Needle,
+    /// Restrict output to rows containing a match, plus `context` rows of
+    /// padding before/after each hit — a binary `grep -C`.
+    pub only_matches: bool,
+    pub context: usize,
+}
+
+/// Finds all non-overlapping occurrences of `needle` in `data`, seeded by
+/// a `memchr` scan for the needle's first byte.
+fn find_matches(data: &[u8], needle: &[u8]) -> Vec<Range<usize>> {
+    if needle.is_empty() || needle.len() > data.len() {
+        return Vec::new();
+    }
+    let mut ranges = Vec::new();
+    let mut pos = 0;
+    let search_end = data.len() - needle.len() + 1;
+    while pos < search_end {
+        match memchr(needle[0], &data[pos..search_end]) {
+            Some(rel) => {
+                let start = pos + rel;
+                if &data[start..start + needle.len()] == needle {
+                    ranges.push(start..start + needle.len());
+                    pos = start + needle.len();
+                } else {
+                    pos = start + 1;
+                }
+            }
+            None => break,
+        }
+    }
+    ranges
+}
+
+fn is_matched(ranges: &[Range<usize>], byte_idx: usize) -> bool {
+    ranges.iter().any(|r| r.contains(&byte_idx))
+}
+
+pub fn render(
+    data: &[u8],
+    head: Option<usize>,
+    tail: Option<usize>,
+    search: Option<&SearchSpec>,
+    theme: &Theme,
+    out: &Output,
+) {
+    let mut emitter = AnsiEmitter::new(theme, out);
+    render_with_emitter(data, head, tail, search, &mut emitter);
+}
+
+/// Same 16-bytes-per-line layout as `render`, but writes through `emitter`
+/// instead of calling `Output` directly — pass a `JsonEmitter` to get one
+/// record per offset/byte/ASCII-char token instead of an ANSI dump.
+pub fn render_with_emitter(
+    data: &[u8],
+    head: Option<usize>,
+    tail: Option<usize>,
+    search: Option<&SearchSpec>,
+    emitter: &mut dyn Emitter,
+) {
     let total_lines = (data.len() + BYTES_PER_LINE - 1) / BYTES_PER_LINE;
 
     let (start_line, end_line) = if let Some(n) = head {
@@ -14,44 +114,217 @@ pub fn render(data: &[u8], head: Option<usize>, tail: Option<usize>, theme: &The
         (0, total_lines)
     };
 
-    for line_idx in start_line..end_line {
-        let offset = line_idx * BYTES_PER_LINE;
-        let chunk_end = (offset + BYTES_PER_LINE).min(data.len());
-        let chunk = &data[offset..chunk_end];
+    let match_ranges = search.map(|s| find_matches(data, s.needle.as_bytes())).unwrap_or_default();
 
-        out.dim(&format!("{:08x}", offset), theme.hex_offset);
-        out.colored(" │ ", theme.line_number);
+    let lines: Vec<usize> = match search {
+        Some(s) if s.only_matches => {
+            let mut shown = BTreeSet::new();
+            for r in &match_ranges {
+                let first = (r.start / BYTES_PER_LINE).max(start_line);
+                let last = ((r.end - 1) / BYTES_PER_LINE).min(end_line.saturating_sub(1));
+                for line in first..=last {
+                    let ctx_start = line.saturating_sub(s.context).max(start_line);
+                    let ctx_end = (line + s.context).min(end_line.saturating_sub(1));
+                    shown.extend(ctx_start..=ctx_end);
+                }
+            }
+            shown.into_iter().collect()
+        }
+        _ => (start_line..end_line).collect(),
+    };
 
-        for i in 0..BYTES_PER_LINE {
-            if i > 0 && i % 4 == 0 {
-                print!(" ");
+    let mut prev: Option<usize> = None;
+    for line_idx in lines {
+        if let Some(p) = prev {
+            if line_idx > p + 1 {
+                emitter.emit(TokenKind::Elision, Span { line: p, col_start: 0, col_end: 2 }, "--");
+                emitter.end_line();
             }
-            if i < chunk.len() {
-                let b = chunk[i];
-                if b == 0 {
-                    out.dim("00 ", theme.hex_byte);
-                } else {
-                    out.colored(&format!("{:02x} ", b), theme.hex_byte);
-                }
+        }
+        prev = Some(line_idx);
+        render_row(line_idx, data, &match_ranges, emitter);
+    }
+}
+
+fn render_row(line_idx: usize, data: &[u8], match_ranges: &[Range<usize>], emitter: &mut dyn Emitter) {
+    let offset = line_idx * BYTES_PER_LINE;
+    let chunk_end = (offset + BYTES_PER_LINE).min(data.len());
+    let chunk = &data[offset..chunk_end];
+
+    let offset_text = format!("{:08x}", offset);
+    let mut col = offset_text.len();
+    emitter.emit(TokenKind::HexOffset, Span { line: line_idx, col_start: 0, col_end: col }, &offset_text);
+    emitter.literal(" │ ");
+    col += 3;
+
+    for i in 0..BYTES_PER_LINE {
+        if i > 0 && i % 4 == 0 {
+            emitter.literal(" ");
+            col += 1;
+        }
+        if i < chunk.len() {
+            let byte_text = format!("{:02x} ", chunk[i]);
+            let kind = if is_matched(match_ranges, offset + i) { TokenKind::HexByteMatch } else { TokenKind::HexByte };
+            emitter.emit(kind, Span { line: line_idx, col_start: col, col_end: col + byte_text.len() }, &byte_text);
+            col += byte_text.len();
+        } else {
+            emitter.literal("   ");
+            col += 3;
+        }
+    }
+
+    emitter.literal("│ ");
+    col += 2;
+
+    for (i, &b) in chunk.iter().enumerate() {
+        let ch = if (0x20..=0x7e).contains(&b) { b as char } else { '.' };
+        let text = ch.to_string();
+        let kind = if is_matched(match_ranges, offset + i) { TokenKind::HexAsciiMatch } else { TokenKind::HexAscii };
+        emitter.emit(kind, Span { line: line_idx, col_start: col, col_end: col + 1 }, &text);
+        col += 1;
+    }
+
+    emitter.end_line();
+}
+
+/// Binary comparison view: `a` and `b` shown stacked row-by-row in the
+/// same 16-bytes-per-line layout `render` uses, rather than side by side —
+/// two full hex-dump rows already run close to the terminal width, so
+/// doubling them up horizontally would just wrap. Bytes that match in
+/// both files use the normal `hex_byte`/`hex_ascii` color; bytes that
+/// differ are colored `diff_changed` in both rows, and bytes past the end
+/// of the shorter file are colored `diff_removed` (only in `a`) or
+/// `diff_added` (only in `b`). When `collapse_identical` is set, a run of
+/// two or more fully-identical rows is replaced by a single `*` line, in
+/// the style of `cmp`/`xxd` diff output.
+pub fn render_diff(a: &[u8], b: &[u8], head: Option<usize>, tail: Option<usize>, collapse_identical: bool, theme: &Theme, out: &Output) {
+    let mut emitter = AnsiEmitter::new(theme, out);
+    render_diff_with_emitter(a, b, head, tail, collapse_identical, &mut emitter);
+}
+
+/// Same comparison as `render_diff`, but writes through `emitter` instead
+/// of calling `Output` directly.
+pub fn render_diff_with_emitter(
+    a: &[u8],
+    b: &[u8],
+    head: Option<usize>,
+    tail: Option<usize>,
+    collapse_identical: bool,
+    emitter: &mut dyn Emitter,
+) {
+    let total_lines = (a.len().max(b.len()) + BYTES_PER_LINE - 1) / BYTES_PER_LINE;
+
+    let (start_line, end_line) = if let Some(n) = head {
+        (0, n.min(total_lines))
+    } else if let Some(n) = tail {
+        (total_lines.saturating_sub(n), total_lines)
+    } else {
+        (0, total_lines)
+    };
+
+    let mut line_idx = start_line;
+    while line_idx < end_line {
+        let a_chunk = diff_chunk_at(a, line_idx);
+        let b_chunk = diff_chunk_at(b, line_idx);
+
+        if collapse_identical && a_chunk == b_chunk {
+            let run_start = line_idx;
+            while line_idx < end_line && diff_chunk_at(a, line_idx) == diff_chunk_at(b, line_idx) {
+                line_idx += 1;
+            }
+            if line_idx - run_start >= 2 {
+                emitter.emit(TokenKind::Elision, Span { line: run_start, col_start: 0, col_end: 1 }, "*");
+                emitter.end_line();
             } else {
-                print!("   ");
-                if i > 0 && i % 4 == 0 {
-                    // already printed group space above
-                }
+                render_diff_row(run_start, a_chunk, b_chunk, emitter);
             }
+            continue;
         }
 
-        out.colored("│ ", theme.line_number);
+        render_diff_row(line_idx, a_chunk, b_chunk, emitter);
+        line_idx += 1;
+    }
+}
 
-        for &b in chunk {
-            let ch = if (0x20..=0x7e).contains(&b) {
-                b as char
-            } else {
-                '.'
-            };
-            out.colored(&ch.to_string(), theme.hex_ascii);
+fn diff_chunk_at(data: &[u8], line_idx: usize) -> &[u8] {
+    let offset = line_idx * BYTES_PER_LINE;
+    if offset >= data.len() {
+        &[]
+    } else {
+        &data[offset..(offset + BYTES_PER_LINE).min(data.len())]
+    }
+}
+
+fn render_diff_row(line_idx: usize, a_chunk: &[u8], b_chunk: &[u8], emitter: &mut dyn Emitter) {
+    render_diff_panel(line_idx, a_chunk, b_chunk, true, emitter);
+    emitter.end_line();
+    render_diff_panel(line_idx, a_chunk, b_chunk, false, emitter);
+    emitter.end_line();
+}
+
+fn render_diff_panel(line_idx: usize, a_chunk: &[u8], b_chunk: &[u8], is_a: bool, emitter: &mut dyn Emitter) {
+    let (chunk, other) = if is_a { (a_chunk, b_chunk) } else { (b_chunk, a_chunk) };
+
+    if is_a {
+        let offset_text = format!("{:08x}", line_idx * BYTES_PER_LINE);
+        emitter.emit(
+            TokenKind::HexOffset,
+            Span { line: line_idx, col_start: 0, col_end: offset_text.len() },
+            &offset_text,
+        );
+    } else {
+        emitter.literal("        ");
+    }
+    emitter.literal(" │ ");
+    let mut col = 8 + 3;
+
+    for i in 0..BYTES_PER_LINE {
+        if i > 0 && i % 4 == 0 {
+            emitter.literal(" ");
+            col += 1;
+        }
+        if i < chunk.len() {
+            let byte_text = format!("{:02x} ", chunk[i]);
+            emitter.emit(
+                diff_kind(i, other.len(), i < other.len() && chunk[i] != other[i], is_a, false),
+                Span { line: line_idx, col_start: col, col_end: col + byte_text.len() },
+                &byte_text,
+            );
+            col += byte_text.len();
+        } else {
+            emitter.literal("   ");
+            col += 3;
         }
+    }
+
+    emitter.literal("│ ");
+    col += 2;
 
-        println!();
+    for (i, &b) in chunk.iter().enumerate() {
+        let ch = if (0x20..=0x7e).contains(&b) { b as char } else { '.' };
+        let text = ch.to_string();
+        emitter.emit(
+            diff_kind(i, other.len(), i < other.len() && b != other[i], is_a, true),
+            Span { line: line_idx, col_start: col, col_end: col + 1 },
+            &text,
+        );
+        col += 1;
+    }
+}
+
+fn diff_kind(i: usize, other_len: usize, changed: bool, is_a: bool, ascii: bool) -> TokenKind {
+    if i >= other_len {
+        match (is_a, ascii) {
+            (true, false) => TokenKind::HexByteRemoved,
+            (true, true) => TokenKind::HexAsciiRemoved,
+            (false, false) => TokenKind::HexByteAdded,
+            (false, true) => TokenKind::HexAsciiAdded,
+        }
+    } else if changed {
+        if ascii { TokenKind::HexAsciiChanged } else { TokenKind::HexByteChanged }
+    } else if ascii {
+        TokenKind::HexAscii
+    } else {
+        TokenKind::HexByte
     }
 }
@@ -6,10 +6,14 @@
 //! - Format detection and decoding
 //! - Smart resizing with aspect ratio correction
 //! - Alpha compositing against terminal background
-//! - Animated image handling (first frame)
+//! - Animated GIF/WebP playback (every frame, with its delay) via
+//!   `load_animated`; single-image loads still take the first frame only
 
-use image::{DynamicImage, GenericImageView, ImageFormat, Rgba, RgbaImage};
+use image::{AnimationDecoder, DynamicImage, Frame, GenericImageView, ImageFormat, Rgba, RgbaImage};
+use std::fs::File;
+use std::io::BufReader;
 use std::path::Path;
+use std::time::Duration;
 
 pub struct DecodedImage {
     /// RGBA pixel data, row-major
@@ -98,16 +102,68 @@ pub fn load_and_prepare(path: &Path, max_width: u32, term_width: u16) -> Result<
 }
 
 pub fn load_from_memory(data: &[u8], max_width: u32, term_width: u16) -> Result<DecodedImage, String> {
-    let img = if let Ok(fmt) = image::guess_format(data) {
-        image::load_from_memory_with_format(data, fmt)
-    } else {
-        image::load_from_memory(data)
-    }
-    .map_err(|e| format!("{}", e))?;
+    let entry = super::registry::by_magic(data);
+
+    let img = match entry {
+        Some(e) => image::load_from_memory_with_format(data, e.format)
+            .map_err(|err| format!("unsupported image format: {} ({})", e.label, err))?,
+        None => image::load_from_memory(data).map_err(|e| format!("unsupported image format: {}", e))?,
+    };
 
     prepare_image(img, max_width, term_width)
 }
 
+/// Decodes every frame of an animated GIF or WebP, pairing each with its
+/// display delay. Returns `Err` for anything that isn't GIF/WebP, or that
+/// decodes to a single frame (callers should fall back to `load_and_prepare`
+/// in that case).
+pub fn load_animated(path: &Path, max_width: u32, term_width: u16) -> Result<Vec<(DecodedImage, Duration)>, String> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|s| s.to_lowercase());
+
+    let file = File::open(path).map_err(|e| format!("{}", e))?;
+    let reader = BufReader::new(file);
+
+    let frames: Vec<Frame> = match ext.as_deref() {
+        Some("gif") => {
+            let decoder = image::codecs::gif::GifDecoder::new(reader).map_err(|e| format!("{}", e))?;
+            decoder
+                .into_frames()
+                .collect_frames()
+                .map_err(|e| format!("{}", e))?
+        }
+        Some("webp") => {
+            let decoder = image::codecs::webp::WebPDecoder::new(reader).map_err(|e| format!("{}", e))?;
+            decoder
+                .into_frames()
+                .collect_frames()
+                .map_err(|e| format!("{}", e))?
+        }
+        _ => return Err("not an animated format".to_string()),
+    };
+
+    if frames.len() <= 1 {
+        return Err("single frame".to_string());
+    }
+
+    let mut out = Vec::with_capacity(frames.len());
+    for frame in frames {
+        let (numer, denom) = frame.delay().numer_denom_ms();
+        let delay = if denom == 0 {
+            Duration::from_millis(100)
+        } else {
+            Duration::from_millis((numer / denom) as u64)
+        };
+
+        let decoded = prepare_image(DynamicImage::ImageRgba8(frame.into_buffer()), max_width, term_width)?;
+        out.push((decoded, delay));
+    }
+
+    Ok(out)
+}
+
 fn prepare_image(img: DynamicImage, max_width: u32, term_width: u16) -> Result<DecodedImage, String> {
     let (orig_w, orig_h) = img.dimensions();
     let (disp_w, disp_h) = calculate_display_size(orig_w, orig_h, max_width, term_width);
@@ -161,24 +217,8 @@ fn calculate_display_size(orig_w: u32, orig_h: u32, max_width: u32, term_width:
 }
 
 fn format_from_path(path: &Path) -> Option<ImageFormat> {
-    let ext = path.extension()?.to_str()?.to_lowercase();
-
-    match ext.as_str() {
-        "png" => Some(ImageFormat::Png),
-        "jpg" | "jpeg" => Some(ImageFormat::Jpeg),
-        "gif" => Some(ImageFormat::Gif),
-        "webp" => Some(ImageFormat::WebP),
-        "bmp" => Some(ImageFormat::Bmp),
-        "tiff" | "tif" => Some(ImageFormat::Tiff),
-        "tga" => Some(ImageFormat::Tga),
-        "ico" => Some(ImageFormat::Ico),
-        "qoi" => Some(ImageFormat::Qoi),
-        "exr" => Some(ImageFormat::OpenExr),
-        "hdr" => Some(ImageFormat::Hdr),
-        "ppm" | "pgm" | "pbm" | "pam" => Some(ImageFormat::Pnm),
-        "ff" => Some(ImageFormat::Farbfeld),
-        _ => None,
-    }
+    let ext = path.extension()?.to_str()?;
+    super::registry::by_extension(ext).map(|e| e.format)
 }
 
 #[cfg(test)]
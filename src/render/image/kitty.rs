@@ -0,0 +1,133 @@
+#![allow(dead_code)]
+//! Kitty graphics protocol backend for the image renderer.
+//!
+//! Transmits raw RGBA pixel data to terminals that implement the Kitty
+//! graphics protocol (Kitty, Ghostty, WezTerm), giving pixel-perfect
+//! images instead of the sixel/half-block approximations. Detection mirrors
+//! `sixel::supports_sixel`'s raw-mode query pattern — see that function's
+//! doc comment for the background-thread caveat.
+
+use std::io::{IsTerminal, Read, Write};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use super::decoder::DecodedImage;
+use crate::output::Output;
+
+/// Each base64 chunk transmitted to the terminal must be at most this many
+/// bytes, per the Kitty graphics protocol spec.
+const CHUNK_SIZE: usize = 4096;
+
+/// Queries Kitty graphics protocol support (`ESC_Gi=1,a=q ESC\`) and checks
+/// the response for `OK`.
+pub fn supports_kitty() -> bool {
+    if !std::io::stdout().is_terminal() {
+        return false;
+    }
+    if crossterm::terminal::enable_raw_mode().is_err() {
+        return false;
+    }
+
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut response = Vec::new();
+        let mut byte = [0u8; 1];
+        let mut stdin = std::io::stdin();
+        while response.len() < 128 {
+            match stdin.read(&mut byte) {
+                Ok(1) => {
+                    response.push(byte[0]);
+                    if byte[0] == b'\\' && response.len() >= 2 && response[response.len() - 2] == 0x1b {
+                        break;
+                    }
+                }
+                _ => break,
+            }
+        }
+        let _ = tx.send(response);
+    });
+
+    print!("\x1b_Gi=1,a=q\x1b\\");
+    let _ = std::io::stdout().flush();
+
+    let supported = match rx.recv_timeout(Duration::from_millis(200)) {
+        Ok(response) => String::from_utf8_lossy(&response).contains("OK"),
+        Err(_) => false,
+    };
+
+    let _ = crossterm::terminal::disable_raw_mode();
+    supported
+}
+
+/// Transmits `img`'s RGBA pixels to the terminal as a Kitty graphics
+/// protocol image, writing the escape-sequence chunks directly through
+/// `out`.
+pub fn render(img: &DecodedImage, out: &Output) {
+    let mut raw = Vec::with_capacity(img.pixels.len() * 4);
+    for p in &img.pixels {
+        raw.push(p.r);
+        raw.push(p.g);
+        raw.push(p.b);
+        raw.push(p.a);
+    }
+
+    let payload = base64_encode(&raw);
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(CHUNK_SIZE).collect();
+    let chunk_count = chunks.len();
+
+    for (i, chunk) in chunks.into_iter().enumerate() {
+        let more = if i + 1 < chunk_count { 1 } else { 0 };
+        let payload = std::str::from_utf8(chunk).unwrap_or("");
+
+        if i == 0 {
+            out.raw(&format!(
+                "\x1b_Ga=T,f=32,s={},v={},m={};{}\x1b\\",
+                img.display_width, img.display_height, more, payload
+            ));
+        } else {
+            out.raw(&format!("\x1b_Gm={};{}\x1b\\", more, payload));
+        }
+    }
+    out.newline();
+}
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity((data.len() + 2) / 3 * 4);
+
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = *chunk.get(1).unwrap_or(&0);
+        let b2 = *chunk.get(2).unwrap_or(&0);
+        let n = ((b0 as u32) << 16) | ((b1 as u32) << 8) | (b2 as u32);
+
+        out.push(ALPHABET[((n >> 18) & 0x3F) as usize] as char);
+        out.push(ALPHABET[((n >> 12) & 0x3F) as usize] as char);
+        out.push(if chunk.len() > 1 {
+            ALPHABET[((n >> 6) & 0x3F) as usize] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            ALPHABET[(n & 0x3F) as usize] as char
+        } else {
+            '='
+        });
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_encode_known_vectors() {
+        assert_eq!(base64_encode(b"Man"), "TWFu");
+        assert_eq!(base64_encode(b"Ma"), "TWE=");
+        assert_eq!(base64_encode(b"M"), "TQ==");
+        assert_eq!(base64_encode(b""), "");
+    }
+}
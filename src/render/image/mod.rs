@@ -1,89 +1,93 @@
 #![allow(dead_code)]
 //! Image rendering module for vita
 //!
-//! Supports: PNG, JPEG, WebP, GIF, BMP, TIFF, TGA, QOI, ICO, EXR, PPM
+//! Supports: PNG, JPEG, WebP, GIF, BMP, TIFF, TGA, QOI, ICO, EXR, PPM, AVIF, DDS
 //!
 //! Architecture:
-//!   mod.rs      - Public API, format support
+//!   mod.rs      - Public API, format support, animation playback loop
+//!   registry.rs - Single source of truth for extension/magic-byte → format
 //!   decoder.rs  - Loading, resizing, preprocessing
 //!   renderer.rs - Half-block terminal rendering
+//!   sixel.rs    - Sixel terminal rendering (quantization, encoding,
+//!                 capability detection)
+//!   kitty.rs    - Kitty graphics protocol rendering (raw RGBA transfer,
+//!                 capability detection)
 
 mod decoder;
+mod kitty;
+mod registry;
 mod renderer;
+mod sixel;
+
+/// Fallback terminal background used when the terminal doesn't answer the
+/// OSC 11 background-color query (see `Output::query_terminal_bg`).
+const DEFAULT_TERMINAL_BG: (u8, u8, u8) = (26, 26, 46);
 
 use std::path::Path;
+use std::time::Duration;
+
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
 
 use crate::output::Output;
 use crate::theme::Theme;
 
-const IMAGE_EXTENSIONS: &[&str] = &[
-    "png", "jpg", "jpeg", "webp", "gif", "bmp", "tiff", "tif", "tga", "ico", "qoi", "exr",
-    "ppm", "pgm", "pbm", "pam", "ff", "hdr",
-];
-
+/// Whether `path`'s extension is a registered image format.
 pub fn is_supported(path: &Path) -> bool {
     path.extension()
         .and_then(|e| e.to_str())
-        .map(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .map(|ext| registry::by_extension(ext).is_some())
         .unwrap_or(false)
 }
 
+/// Whether `buf`'s leading bytes match a registered image format's magic
+/// signature.
 pub fn is_image_magic(buf: &[u8]) -> bool {
-    if buf.len() < 12 {
-        return false;
-    }
-
-    // PNG: 89 50 4E 47 0D 0A 1A 0A
-    if buf.starts_with(&[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A]) {
-        return true;
-    }
-
-    // JPEG: FF D8 FF
-    if buf.starts_with(&[0xFF, 0xD8, 0xFF]) {
-        return true;
-    }
-
-    // GIF: GIF87a / GIF89a
-    if buf.starts_with(b"GIF87a") || buf.starts_with(b"GIF89a") {
-        return true;
-    }
+    registry::by_magic(buf).is_some()
+}
 
-    // WebP: RIFF....WEBP
-    if buf.len() >= 12 && &buf[0..4] == b"RIFF" && &buf[8..12] == b"WEBP" {
-        return true;
+/// Renders via the Kitty graphics protocol when the terminal advertises
+/// support (pixel-perfect, no quantization), else sixel, else falls back to
+/// the half-block approximation. Sixel and half-block both composite
+/// transparency against `out.query_terminal_bg()`'s real reading, falling
+/// back to `theme`'s default when the terminal doesn't answer.
+fn render_decoded(decoded: &decoder::DecodedImage, theme: &Theme, out: &Output) {
+    if kitty::supports_kitty() {
+        kitty::render(decoded, out);
+        return;
     }
 
-    // BMP: BM
-    if buf.starts_with(b"BM") {
-        return true;
-    }
+    let bg = out.query_terminal_bg().unwrap_or_else(|| theme_bg(theme));
 
-    // TIFF: II (little-endian) or MM (big-endian)
-    if (buf[0] == 0x49 && buf[1] == 0x49 && buf[2] == 0x2A && buf[3] == 0x00)
-        || (buf[0] == 0x4D && buf[1] == 0x4D && buf[2] == 0x00 && buf[3] == 0x2A)
-    {
-        return true;
-    }
-
-    // QOI: qoif
-    if buf.starts_with(b"qoif") {
-        return true;
+    if sixel::supports_sixel() {
+        out.raw(&sixel::to_sixel(decoded, bg));
+        out.newline();
+    } else {
+        renderer::render_halfblock(decoded, bg, out);
     }
+}
 
-    // EXR: 76 2F 31 01
-    if buf.starts_with(&[0x76, 0x2F, 0x31, 0x01]) {
-        return true;
+/// Falls back to `theme.terminal_bg` when the terminal didn't answer the
+/// OSC 11 query, or `DEFAULT_TERMINAL_BG` if the theme color isn't RGB.
+fn theme_bg(theme: &Theme) -> (u8, u8, u8) {
+    match theme.terminal_bg {
+        crossterm::style::Color::Rgb { r, g, b } => (r, g, b),
+        _ => DEFAULT_TERMINAL_BG,
     }
+}
 
-    // ICO: 00 00 01 00
-    if buf.starts_with(&[0x00, 0x00, 0x01, 0x00]) {
-        return true;
+/// Renders `path`. Animated GIF/WebP loop in place (one terminal redraw per
+/// frame) unless `no_anim` is set, in which case only the first frame is
+/// shown; `max_loops` caps the number of times the animation replays
+/// (`None` loops until Ctrl-C).
+pub fn render(path: &Path, max_width: u32, max_loops: Option<usize>, no_anim: bool, theme: &Theme, out: &Output) {
+    if !no_anim {
+        if let Ok(frames) = decoder::load_animated(path, max_width, out.term_width) {
+            out.newline();
+            render_frames(&frames, max_loops, theme, out);
+            return;
+        }
     }
 
-    false
-}
-
-pub fn render(path: &Path, max_width: u32, theme: &Theme, out: &Output) {
     let decoded = match decoder::load_and_prepare(path, max_width, out.term_width) {
         Ok(img) => img,
         Err(e) => {
@@ -92,8 +96,8 @@ pub fn render(path: &Path, max_width: u32, theme: &Theme, out: &Output) {
         }
     };
 
-    println!();
-    renderer::render_halfblock(&decoded, out);
+    out.newline();
+    render_decoded(&decoded, theme, out);
 
     let fmt_name = path
         .extension()
@@ -114,6 +118,61 @@ pub fn render(path: &Path, max_width: u32, theme: &Theme, out: &Output) {
     );
 }
 
+/// Loops `frames` (each with its decode delay) until `max_loops` replays
+/// have completed or Ctrl-C is pressed, repositioning the cursor up by the
+/// frame's row count between redraws so each frame overwrites the last.
+fn render_frames(frames: &[(decoder::DecodedImage, Duration)], max_loops: Option<usize>, theme: &Theme, out: &Output) {
+    let Some(first) = frames.first() else {
+        return;
+    };
+    let bg = out.query_terminal_bg().unwrap_or_else(|| theme_bg(theme));
+    let raw_mode_enabled = crossterm::terminal::enable_raw_mode().is_ok();
+
+    let mut loops = 0usize;
+    let mut last_rows = (first.0.display_height / 2) as u16;
+
+    'playback: loop {
+        for (frame, delay) in frames {
+            renderer::render_halfblock(frame, bg, out);
+            out.flush();
+            last_rows = (frame.display_height / 2) as u16;
+
+            if wait_for_ctrl_c(*delay) {
+                break 'playback;
+            }
+
+            out.raw(&format!("\x1b[{}A", last_rows));
+        }
+
+        loops += 1;
+        if max_loops.map(|max| loops >= max).unwrap_or(false) {
+            break;
+        }
+    }
+
+    if raw_mode_enabled {
+        let _ = crossterm::terminal::disable_raw_mode();
+    }
+
+    // Leave the cursor below the last-drawn frame so following output
+    // doesn't overdraw it.
+    out.raw(&format!("\x1b[{}B", last_rows));
+    out.flush();
+}
+
+/// Waits up to `delay` for a Ctrl-C key event, returning `true` if one
+/// arrived. Requires raw mode (enabled by the caller) to see the keypress
+/// instead of it being consumed by line buffering.
+fn wait_for_ctrl_c(delay: Duration) -> bool {
+    match event::poll(delay) {
+        Ok(true) => matches!(
+            event::read(),
+            Ok(Event::Key(k)) if k.code == KeyCode::Char('c') && k.modifiers.contains(KeyModifiers::CONTROL)
+        ),
+        _ => false,
+    }
+}
+
 pub fn render_bytes(data: &[u8], max_width: u32, theme: &Theme, out: &Output) {
     let decoded = match decoder::load_from_memory(data, max_width, out.term_width) {
         Ok(img) => img,
@@ -123,8 +182,8 @@ pub fn render_bytes(data: &[u8], max_width: u32, theme: &Theme, out: &Output) {
         }
     };
 
-    println!();
-    renderer::render_halfblock(&decoded, out);
+    out.newline();
+    render_decoded(&decoded, theme, out);
 
     out.dim(
         &format!(
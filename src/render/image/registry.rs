@@ -0,0 +1,166 @@
+//! Single source of truth mapping file extensions and magic-byte
+//! signatures to `image::ImageFormat`.
+//!
+//! `detect::detect_format`'s `FileFormat::Image` classification, the parent
+//! `image` module's `is_supported`/`is_image_magic`, and `decoder`'s loader all
+//! consult this table instead of keeping their own extension/signature
+//! lists, so adding a new codec (like AVIF/DDS below) only means adding one
+//! entry here.
+
+use image::ImageFormat;
+
+pub struct Entry {
+    pub extensions: &'static [&'static str],
+    pub format: ImageFormat,
+    pub magic: fn(&[u8]) -> bool,
+    /// Human-readable name used in "unsupported image format" errors.
+    pub label: &'static str,
+}
+
+pub const ENTRIES: &[Entry] = &[
+    Entry {
+        extensions: &["png"],
+        format: ImageFormat::Png,
+        magic: |b| b.starts_with(&[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A]),
+        label: "PNG",
+    },
+    Entry {
+        extensions: &["jpg", "jpeg"],
+        format: ImageFormat::Jpeg,
+        magic: |b| b.starts_with(&[0xFF, 0xD8, 0xFF]),
+        label: "JPEG",
+    },
+    Entry {
+        extensions: &["gif"],
+        format: ImageFormat::Gif,
+        magic: |b| b.starts_with(b"GIF87a") || b.starts_with(b"GIF89a"),
+        label: "GIF",
+    },
+    Entry {
+        extensions: &["webp"],
+        format: ImageFormat::WebP,
+        magic: |b| b.len() >= 12 && &b[0..4] == b"RIFF" && &b[8..12] == b"WEBP",
+        label: "WebP",
+    },
+    Entry {
+        extensions: &["bmp"],
+        format: ImageFormat::Bmp,
+        magic: |b| b.starts_with(b"BM"),
+        label: "BMP",
+    },
+    Entry {
+        extensions: &["tiff", "tif"],
+        format: ImageFormat::Tiff,
+        magic: |b| {
+            b.len() >= 4
+                && ((b[0] == 0x49 && b[1] == 0x49 && b[2] == 0x2A && b[3] == 0x00)
+                    || (b[0] == 0x4D && b[1] == 0x4D && b[2] == 0x00 && b[3] == 0x2A))
+        },
+        label: "TIFF",
+    },
+    Entry {
+        extensions: &["tga"],
+        format: ImageFormat::Tga,
+        // TGA has no reliable magic signature; extension is the only signal.
+        magic: |_| false,
+        label: "TGA",
+    },
+    Entry {
+        extensions: &["ico"],
+        format: ImageFormat::Ico,
+        magic: |b| b.starts_with(&[0x00, 0x00, 0x01, 0x00]),
+        label: "ICO",
+    },
+    Entry {
+        extensions: &["qoi"],
+        format: ImageFormat::Qoi,
+        magic: |b| b.starts_with(b"qoif"),
+        label: "QOI",
+    },
+    Entry {
+        extensions: &["exr"],
+        format: ImageFormat::OpenExr,
+        magic: |b| b.starts_with(&[0x76, 0x2F, 0x31, 0x01]),
+        label: "OpenEXR",
+    },
+    Entry {
+        extensions: &["hdr"],
+        format: ImageFormat::Hdr,
+        magic: |b| b.starts_with(b"#?RADIANCE") || b.starts_with(b"#?RGBE"),
+        label: "Radiance HDR",
+    },
+    Entry {
+        extensions: &["ppm", "pgm", "pbm", "pam"],
+        format: ImageFormat::Pnm,
+        magic: |b| {
+            b.len() >= 2
+                && b[0] == b'P'
+                && matches!(b[1], b'1' | b'2' | b'3' | b'4' | b'5' | b'6' | b'7')
+        },
+        label: "PNM",
+    },
+    Entry {
+        extensions: &["ff"],
+        format: ImageFormat::Farbfeld,
+        magic: |b| b.starts_with(b"farbfeld"),
+        label: "farbfeld",
+    },
+    Entry {
+        extensions: &["avif"],
+        format: ImageFormat::Avif,
+        magic: |b| b.len() >= 12 && &b[4..8] == b"ftyp" && &b[8..12] == b"avif",
+        label: "AVIF",
+    },
+    Entry {
+        extensions: &["dds"],
+        format: ImageFormat::Dds,
+        magic: |b| b.starts_with(&[0x44, 0x44, 0x53, 0x20]),
+        label: "DDS",
+    },
+];
+
+pub fn by_extension(ext: &str) -> Option<&'static Entry> {
+    let ext = ext.to_lowercase();
+    ENTRIES.iter().find(|e| e.extensions.contains(&ext.as_str()))
+}
+
+pub fn by_magic(buf: &[u8]) -> Option<&'static Entry> {
+    ENTRIES.iter().find(|e| (e.magic)(buf))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_by_extension_case_insensitive() {
+        assert!(by_extension("PNG").is_some());
+        assert!(by_extension("avif").is_some());
+        assert!(by_extension("txt").is_none());
+    }
+
+    #[test]
+    fn test_by_magic_png() {
+        let sig = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0];
+        assert_eq!(by_magic(&sig).unwrap().label, "PNG");
+    }
+
+    #[test]
+    fn test_by_magic_avif() {
+        let mut buf = vec![0u8; 12];
+        buf[4..8].copy_from_slice(b"ftyp");
+        buf[8..12].copy_from_slice(b"avif");
+        assert_eq!(by_magic(&buf).unwrap().label, "AVIF");
+    }
+
+    #[test]
+    fn test_by_magic_dds() {
+        let sig = [0x44, 0x44, 0x53, 0x20, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(by_magic(&sig).unwrap().label, "DDS");
+    }
+
+    #[test]
+    fn test_by_magic_unrecognized() {
+        assert!(by_magic(b"not an image").is_none());
+    }
+}
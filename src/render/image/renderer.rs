@@ -12,9 +12,11 @@
 //!   - Skips redundant ANSI escape codes when colors don't change
 //!   - Handles transparency (composites against terminal background)
 //!   - Batches output for performance
+//!   - Downgrades to 256/16-color escapes on terminals that can't do
+//!     truecolor (`Output::color_depth`)
 
 use super::decoder::{DecodedImage, Pixel};
-use crate::output::Output;
+use crate::output::{self, ColorDepth, Output};
 
 const HALF_UPPER: &str = "▀";
 const HALF_LOWER: &str = "▄";
@@ -22,7 +24,7 @@ const RESET: &str = "\x1b[0m";
 const RESET_BG: &str = "\x1b[49m";
 
 #[allow(unused_assignments)]
-pub fn render_halfblock(img: &DecodedImage, _out: &Output) {
+pub fn render_halfblock(img: &DecodedImage, bg: (u8, u8, u8), out: &Output) {
     let mut buf = String::with_capacity(img.display_width as usize * 64);
 
     // Skip redundant ANSI color codes when adjacent pixels match
@@ -62,7 +64,7 @@ pub fn render_halfblock(img: &DecodedImage, _out: &Output) {
                 // Only bottom visible → ▄ with fg=bottom
                 let fg = (bot.r, bot.g, bot.b);
                 if last_fg != Some(fg) {
-                    write_fg(&mut buf, bot.r, bot.g, bot.b);
+                    write_fg(&mut buf, bot.r, bot.g, bot.b, out.color_depth);
                     last_fg = Some(fg);
                 }
                 buf.push_str(RESET_BG);
@@ -72,7 +74,7 @@ pub fn render_halfblock(img: &DecodedImage, _out: &Output) {
                 // Only top visible → ▀ with fg=top
                 let fg = (top.r, top.g, top.b);
                 if last_fg != Some(fg) {
-                    write_fg(&mut buf, top.r, top.g, top.b);
+                    write_fg(&mut buf, top.r, top.g, top.b, out.color_depth);
                     last_fg = Some(fg);
                 }
                 buf.push_str(RESET_BG);
@@ -80,18 +82,18 @@ pub fn render_halfblock(img: &DecodedImage, _out: &Output) {
                 buf.push_str(HALF_UPPER);
             } else {
                 // Both visible → ▀ with fg=top, bg=bottom
-                let top_c = composite_for_display(top);
-                let bot_c = composite_for_display(bot);
+                let top_c = composite_for_display(top, bg);
+                let bot_c = composite_for_display(bot, bg);
 
                 let fg = (top_c.r, top_c.g, top_c.b);
                 let bg = (bot_c.r, bot_c.g, bot_c.b);
 
                 if last_fg != Some(fg) {
-                    write_fg(&mut buf, top_c.r, top_c.g, top_c.b);
+                    write_fg(&mut buf, top_c.r, top_c.g, top_c.b, out.color_depth);
                     last_fg = Some(fg);
                 }
                 if last_bg != Some(bg) {
-                    write_bg(&mut buf, bot_c.r, bot_c.g, bot_c.b);
+                    write_bg(&mut buf, bot_c.r, bot_c.g, bot_c.b, out.color_depth);
                     last_bg = Some(bg);
                 }
                 buf.push_str(HALF_UPPER);
@@ -100,28 +102,52 @@ pub fn render_halfblock(img: &DecodedImage, _out: &Output) {
 
         buf.push_str(RESET);
         buf.push('\n');
-        print!("{}", buf);
+        out.raw(&buf);
     }
 }
 
-/// Composites against assumed terminal bg (#1a1a2e)
-fn composite_for_display(px: Pixel) -> Pixel {
+/// Composites against the detected (or theme-default) terminal background.
+fn composite_for_display(px: Pixel, bg: (u8, u8, u8)) -> Pixel {
     if px.a >= 250 {
         return px;
     }
-    px.composite_over(26, 26, 46)
+    px.composite_over(bg.0, bg.1, bg.2)
 }
 
 #[inline]
-fn write_fg(buf: &mut String, r: u8, g: u8, b: u8) {
+fn write_fg(buf: &mut String, r: u8, g: u8, b: u8, depth: ColorDepth) {
     use std::fmt::Write;
-    let _ = write!(buf, "\x1b[38;2;{};{};{}m", r, g, b);
+    match depth {
+        ColorDepth::TrueColor => {
+            let _ = write!(buf, "\x1b[38;2;{};{};{}m", r, g, b);
+        }
+        ColorDepth::Ansi256 => {
+            let _ = write!(buf, "\x1b[38;5;{}m", output::nearest_256(r, g, b));
+        }
+        ColorDepth::Ansi16 => {
+            let idx = output::nearest_ansi16_index(r, g, b);
+            let code = if idx < 8 { 30 + idx } else { 90 + (idx - 8) };
+            let _ = write!(buf, "\x1b[{}m", code);
+        }
+    }
 }
 
 #[inline]
-fn write_bg(buf: &mut String, r: u8, g: u8, b: u8) {
+fn write_bg(buf: &mut String, r: u8, g: u8, b: u8, depth: ColorDepth) {
     use std::fmt::Write;
-    let _ = write!(buf, "\x1b[48;2;{};{};{}m", r, g, b);
+    match depth {
+        ColorDepth::TrueColor => {
+            let _ = write!(buf, "\x1b[48;2;{};{};{}m", r, g, b);
+        }
+        ColorDepth::Ansi256 => {
+            let _ = write!(buf, "\x1b[48;5;{}m", output::nearest_256(r, g, b));
+        }
+        ColorDepth::Ansi16 => {
+            let idx = output::nearest_ansi16_index(r, g, b);
+            let code = if idx < 8 { 40 + idx } else { 100 + (idx - 8) };
+            let _ = write!(buf, "\x1b[{}m", code);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -131,7 +157,7 @@ mod tests {
     #[test]
     fn test_composite_opaque() {
         let px = Pixel { r: 255, g: 0, b: 0, a: 255 };
-        let result = composite_for_display(px);
+        let result = composite_for_display(px, (26, 26, 46));
         assert_eq!(result.r, 255);
         assert_eq!(result.g, 0);
     }
@@ -139,7 +165,7 @@ mod tests {
     #[test]
     fn test_composite_semi_transparent() {
         let px = Pixel { r: 255, g: 255, b: 255, a: 128 };
-        let result = composite_for_display(px);
+        let result = composite_for_display(px, (26, 26, 46));
         // Should be lighter than background but not pure white
         assert!(result.r > 100);
         assert!(result.a == 255);
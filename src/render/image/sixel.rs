@@ -0,0 +1,280 @@
+#![allow(dead_code)]
+//! Sixel graphics backend for the image renderer.
+//!
+//! Quantizes the composited image to a <=256 color palette via median-cut,
+//! then emits a sixel stream in 6-pixel-row bands with run-length-encoded
+//! sixel bytes. Gives true per-pixel resolution on sixel-capable terminals
+//! (xterm -ti vt340, mlterm, foot) instead of the half-block approximation
+//! in `renderer.rs`.
+
+use std::fmt::Write as _;
+use std::io::{IsTerminal, Read, Write};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use super::decoder::DecodedImage;
+
+const MAX_COLORS: usize = 256;
+
+/// Queries Primary Device Attributes (`ESC[c`) and checks the response for
+/// the sixel capability marker `;4;`. Only meaningful when stdout is a real
+/// terminal; returns `false` (falling back to half-block rendering) if raw
+/// mode can't be entered or nothing comes back within the timeout.
+///
+/// The response is read on a background thread so a non-responding
+/// terminal can't hang the query; if the timeout fires first, that thread
+/// is simply abandoned — harmless for a short-lived CLI process.
+pub fn supports_sixel() -> bool {
+    if !std::io::stdout().is_terminal() {
+        return false;
+    }
+    if crossterm::terminal::enable_raw_mode().is_err() {
+        return false;
+    }
+
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut response = Vec::new();
+        let mut byte = [0u8; 1];
+        let mut stdin = std::io::stdin();
+        while response.len() < 64 {
+            match stdin.read(&mut byte) {
+                Ok(1) => {
+                    response.push(byte[0]);
+                    if byte[0] == b'c' {
+                        break;
+                    }
+                }
+                _ => break,
+            }
+        }
+        let _ = tx.send(response);
+    });
+
+    print!("\x1b[c");
+    let _ = std::io::stdout().flush();
+
+    let supported = match rx.recv_timeout(Duration::from_millis(200)) {
+        Ok(response) => String::from_utf8_lossy(&response).contains(";4;"),
+        Err(_) => false,
+    };
+
+    let _ = crossterm::terminal::disable_raw_mode();
+    supported
+}
+
+/// Renders `img` as a sixel escape sequence, compositing transparency
+/// against `bg`.
+pub fn to_sixel(img: &DecodedImage, bg: (u8, u8, u8)) -> String {
+    let width = img.display_width as usize;
+    let height = img.display_height as usize;
+
+    let composited: Vec<(u8, u8, u8)> = (0..img.display_height)
+        .flat_map(|y| (0..img.display_width).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let c = img.get_pixel(x, y).composite_over(bg.0, bg.1, bg.2);
+            (c.r, c.g, c.b)
+        })
+        .collect();
+
+    let palette = median_cut(&composited, MAX_COLORS);
+    let indexed: Vec<usize> = composited.iter().map(|&c| nearest(&palette, c)).collect();
+
+    let mut out = String::with_capacity(indexed.len() * 2);
+    out.push_str("\x1bPq");
+
+    for (i, &(r, g, b)) in palette.iter().enumerate() {
+        let _ = write!(out, "#{};2;{};{};{}", i, scale(r), scale(g), scale(b));
+    }
+
+    let mut y = 0;
+    while y < height {
+        let band_height = (height - y).min(6);
+
+        let mut colors_in_band = Vec::new();
+        for row in 0..band_height {
+            for x in 0..width {
+                let idx = indexed[(y + row) * width + x];
+                if !colors_in_band.contains(&idx) {
+                    colors_in_band.push(idx);
+                }
+            }
+        }
+
+        for &color in &colors_in_band {
+            let _ = write!(out, "#{}", color);
+
+            let mut run_byte: Option<u8> = None;
+            let mut run_len = 0usize;
+
+            for x in 0..width {
+                let mut bits = 0u8;
+                for row in 0..band_height {
+                    if indexed[(y + row) * width + x] == color {
+                        bits |= 1 << row;
+                    }
+                }
+                let byte = 0x3F + bits;
+
+                match run_byte {
+                    Some(b) if b == byte => run_len += 1,
+                    Some(b) => {
+                        push_run(&mut out, b, run_len);
+                        run_byte = Some(byte);
+                        run_len = 1;
+                    }
+                    None => {
+                        run_byte = Some(byte);
+                        run_len = 1;
+                    }
+                }
+            }
+            if let Some(b) = run_byte {
+                push_run(&mut out, b, run_len);
+            }
+            out.push('$');
+        }
+
+        out.push('-');
+        y += 6;
+    }
+
+    out.push_str("\x1b\\");
+    out
+}
+
+/// Appends `len` copies of `byte`, using `!<count><byte>` run-length
+/// encoding once it's shorter than repeating the byte literally.
+fn push_run(out: &mut String, byte: u8, len: usize) {
+    if len > 3 {
+        let _ = write!(out, "!{}{}", len, byte as char);
+    } else {
+        for _ in 0..len {
+            out.push(byte as char);
+        }
+    }
+}
+
+/// Sixel color registers are specified 0-100, not 0-255.
+fn scale(v: u8) -> u32 {
+    (v as u32 * 100 + 127) / 255
+}
+
+fn nearest(palette: &[(u8, u8, u8)], c: (u8, u8, u8)) -> usize {
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, &(r, g, b))| {
+            let dr = r as i32 - c.0 as i32;
+            let dg = g as i32 - c.1 as i32;
+            let db = b as i32 - c.2 as i32;
+            dr * dr + dg * dg + db * db
+        })
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// Median-cut color quantization: recursively splits the widest-range
+/// bucket along its widest channel until `max_colors` buckets remain, then
+/// averages each bucket into one palette entry.
+fn median_cut(pixels: &[(u8, u8, u8)], max_colors: usize) -> Vec<(u8, u8, u8)> {
+    if pixels.is_empty() {
+        return vec![(0, 0, 0)];
+    }
+
+    let mut buckets: Vec<Vec<(u8, u8, u8)>> = vec![pixels.to_vec()];
+
+    while buckets.len() < max_colors {
+        let Some((idx, _)) = buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.len() > 1 && channel_range(b) > 0)
+            .max_by_key(|(_, b)| channel_range(b))
+        else {
+            break;
+        };
+
+        let mut bucket = buckets.swap_remove(idx);
+        let channel = widest_channel(&bucket);
+        bucket.sort_by_key(|&(r, g, b)| match channel {
+            0 => r,
+            1 => g,
+            _ => b,
+        });
+
+        let right = bucket.split_off(bucket.len() / 2);
+        buckets.push(bucket);
+        buckets.push(right);
+    }
+
+    buckets.iter().map(|b| average(b)).collect()
+}
+
+fn channel_bounds(bucket: &[(u8, u8, u8)]) -> ((u8, u8, u8), (u8, u8, u8)) {
+    let (mut min, mut max) = ((255u8, 255u8, 255u8), (0u8, 0u8, 0u8));
+    for &(r, g, b) in bucket {
+        min = (min.0.min(r), min.1.min(g), min.2.min(b));
+        max = (max.0.max(r), max.1.max(g), max.2.max(b));
+    }
+    (min, max)
+}
+
+fn channel_range(bucket: &[(u8, u8, u8)]) -> u32 {
+    let (min, max) = channel_bounds(bucket);
+    (max.0 - min.0) as u32 + (max.1 - min.1) as u32 + (max.2 - min.2) as u32
+}
+
+fn widest_channel(bucket: &[(u8, u8, u8)]) -> u8 {
+    let (min, max) = channel_bounds(bucket);
+    let ranges = [(max.0 - min.0, 0u8), (max.1 - min.1, 1u8), (max.2 - min.2, 2u8)];
+    ranges.iter().max_by_key(|(r, _)| *r).map(|&(_, c)| c).unwrap_or(0)
+}
+
+fn average(bucket: &[(u8, u8, u8)]) -> (u8, u8, u8) {
+    let n = bucket.len().max(1) as u32;
+    let (mut sr, mut sg, mut sb) = (0u32, 0u32, 0u32);
+    for &(r, g, b) in bucket {
+        sr += r as u32;
+        sg += g as u32;
+        sb += b as u32;
+    }
+    ((sr / n) as u8, (sg / n) as u8, (sb / n) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median_cut_single_color() {
+        let pixels = vec![(10, 20, 30); 16];
+        let palette = median_cut(&pixels, 256);
+        assert_eq!(palette.len(), 1);
+        assert_eq!(palette[0], (10, 20, 30));
+    }
+
+    #[test]
+    fn test_median_cut_splits_distinct_colors() {
+        let mut pixels = vec![(0, 0, 0); 8];
+        pixels.extend(vec![(255, 255, 255); 8]);
+        let palette = median_cut(&pixels, 2);
+        assert_eq!(palette.len(), 2);
+    }
+
+    #[test]
+    fn test_scale_roundtrip_bounds() {
+        assert_eq!(scale(0), 0);
+        assert_eq!(scale(255), 100);
+    }
+
+    #[test]
+    fn test_push_run_short_and_long() {
+        let mut short = String::new();
+        push_run(&mut short, b'?', 2);
+        assert_eq!(short, "??");
+
+        let mut long = String::new();
+        push_run(&mut long, b'?', 10);
+        assert_eq!(long, "!10?");
+    }
+}
@@ -1,7 +1,11 @@
 //! JSON renderer with rainbow brackets and pastel highlighting
 //!
 //! Nested brackets `{}[]` cycle through pastel rainbow colors,
-//! making nesting depth instantly visible.
+//! making nesting depth instantly visible. Newline-delimited JSON (one
+//! record per line) is detected and rendered record-by-record so the
+//! rainbow depth doesn't drift across the whole stream.
+
+use memchr::{memchr, memchr2};
 
 use crate::output::Output;
 use crate::theme::Theme;
@@ -24,202 +28,214 @@ pub fn render(content: &str, theme: &Theme, out: &Output) {
                 serde_json::to_string_pretty(&value).unwrap_or_else(|_| content.to_string());
             render_highlighted(&pretty, theme, out);
         }
+        Err(_) if is_ndjson(content) => render_ndjson(content, theme, out),
         Err(_) => {
             render_highlighted(content, theme, out);
         }
     }
 }
 
-fn render_highlighted(json: &str, theme: &Theme, out: &Output) {
-    let mut in_string = false;
-    let mut is_key = false;
-    let mut escaped = false;
-    let mut after_colon = false;
-    let mut depth: usize = 0;
-    let chars: Vec<char> = json.chars().collect();
-    let mut i = 0;
+/// Reports whether every non-empty line of `content` parses as its own JSON
+/// value — the newline-delimited JSON convention common in logs and data
+/// pipelines. Only checked once whole-document parsing has already failed,
+/// since a single valid document is handled by the `Ok` branch above.
+fn is_ndjson(content: &str) -> bool {
+    let mut lines = content.lines().filter(|l| !l.trim().is_empty()).peekable();
+    lines.peek().is_some() && lines.all(|l| serde_json::from_str::<serde_json::Value>(l).is_ok())
+}
 
-    while i < chars.len() {
-        let ch = chars[i];
+/// Renders each line as an independent JSON record rather than one malformed
+/// document: bracket-depth rainbow coloring resets at every line (via a
+/// fresh call to `render_highlighted`, whose `depth` is local to the call)
+/// so nesting colors track each record instead of drifting across the whole
+/// stream, and each record gets a dimmed sequential index gutter.
+fn render_ndjson(content: &str, theme: &Theme, out: &Output) {
+    let records = content.lines().filter(|l| !l.trim().is_empty()).count();
+    let num_width = format!("{}", records).len();
+    let mut index = 0;
 
-        if escaped {
-            let color = if is_key {
-                theme.json_key
-            } else {
-                theme.json_string
-            };
-            out.colored(&ch.to_string(), color);
-            escaped = false;
-            i += 1;
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            out.newline();
             continue;
         }
+        index += 1;
+        out.dim(&format!("{:>width$} \u{2502} ", index, width = num_width), theme.line_number);
+        render_highlighted(line, theme, out);
+    }
+}
 
-        if ch == '\\' && in_string {
-            escaped = true;
-            let color = if is_key {
-                theme.json_key
-            } else {
-                theme.json_string
-            };
-            out.colored("\\", color);
-            i += 1;
-            continue;
-        }
+/// Single-pass byte-cursor highlighter: walks `json` once, advancing the
+/// cursor by whole tokens (a string, a number, a keyword) rather than
+/// collecting into a `Vec<char>` and indexing it. Strings are sliced and
+/// printed in one `out.colored` call rather than character-by-character,
+/// which also means "is this string a key" only has to look at what
+/// follows it once — no rescanning the rest of the document per string
+/// like the old `is_likely_key` did.
+///
+/// Also tolerates the handful of JSONC/JSON5 extensions that trip up
+/// `serde_json` — `//`/`/* */` comments, single-quoted strings, and
+/// unquoted identifier keys — since this is the path used to highlight
+/// content that failed strict parsing. None of those constructs occur in
+/// valid JSON, so recognizing them costs nothing on the strict/pretty-
+/// printed path.
+fn render_highlighted(json: &str, theme: &Theme, out: &Output) {
+    let bytes = json.as_bytes();
+    let mut depth: usize = 0;
+    let mut after_colon = false;
+    let mut i = 0;
 
-        if ch == '"' {
-            if !in_string {
-                in_string = true;
-                is_key = !after_colon && is_likely_key(json, i);
-                let color = if is_key {
-                    theme.json_key
-                } else {
-                    theme.json_string
-                };
-                out.colored("\"", color);
-            } else {
-                let color = if is_key {
-                    theme.json_key
-                } else {
-                    theme.json_string
-                };
-                out.colored("\"", color);
-                in_string = false;
-                is_key = false;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' | b'\'' => {
+                let (end, is_key) = scan_string(bytes, i, after_colon);
+                let color = if is_key { theme.json_key } else { theme.json_string };
+                out.colored(&json[i..end], color);
                 after_colon = false;
+                i = end;
             }
-            i += 1;
-            continue;
-        }
-
-        if in_string {
-            let color = if is_key {
-                theme.json_key
-            } else {
-                theme.json_string
-            };
-            out.colored(&ch.to_string(), color);
-            i += 1;
-            continue;
-        }
-
-        match ch {
-            ':' => {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                let end = memchr(b'\n', &bytes[i..]).map(|off| i + off).unwrap_or(bytes.len());
+                out.dim(&json[i..end], theme.text);
+                i = end;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = json[i + 2..]
+                    .find("*/")
+                    .map(|off| i + 2 + off + 2)
+                    .unwrap_or(bytes.len());
+                out.dim(&json[i..end], theme.text);
+                i = end;
+            }
+            b'a'..=b'z' | b'A'..=b'Z' | b'_' | b'$'
+                if !bytes[i..].starts_with(b"true")
+                    && !bytes[i..].starts_with(b"false")
+                    && !bytes[i..].starts_with(b"null") =>
+            {
+                let start = i;
+                while i < bytes.len()
+                    && matches!(bytes[i], b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'$')
+                {
+                    i += 1;
+                }
+                let is_key = !after_colon && is_followed_by_colon(bytes, i);
+                let color = if is_key { theme.json_key } else { theme.text };
+                out.colored(&json[start..i], color);
+                after_colon = false;
+            }
+            b':' => {
                 out.colored(":", theme.json_bracket);
                 after_colon = true;
                 i += 1;
             }
-            ',' => {
+            b',' => {
                 let (r, g, b) = rainbow_color(depth);
                 out.colored(",", crossterm::style::Color::Rgb { r, g, b });
                 after_colon = false;
                 i += 1;
             }
-            '{' | '[' => {
+            ch @ (b'{' | b'[') => {
                 let (r, g, b) = rainbow_color(depth);
-                out.colored(
-                    &ch.to_string(),
-                    crossterm::style::Color::Rgb { r, g, b },
-                );
+                out.colored(&(ch as char).to_string(), crossterm::style::Color::Rgb { r, g, b });
                 depth += 1;
                 after_colon = false;
                 i += 1;
             }
-            '}' | ']' => {
+            ch @ (b'}' | b']') => {
                 depth = depth.saturating_sub(1);
                 let (r, g, b) = rainbow_color(depth);
-                out.colored(
-                    &ch.to_string(),
-                    crossterm::style::Color::Rgb { r, g, b },
-                );
+                out.colored(&(ch as char).to_string(), crossterm::style::Color::Rgb { r, g, b });
                 i += 1;
             }
-            _ if ch.is_ascii_digit() || ch == '-' || ch == '.' => {
+            b'-' | b'.' | b'0'..=b'9' => {
                 let start = i;
-                while i < chars.len()
-                    && (chars[i].is_ascii_digit()
-                        || chars[i] == '.'
-                        || chars[i] == '-'
-                        || chars[i] == '+'
-                        || chars[i] == 'e'
-                        || chars[i] == 'E')
+                while i < bytes.len()
+                    && matches!(bytes[i], b'0'..=b'9' | b'.' | b'-' | b'+' | b'e' | b'E')
                 {
                     i += 1;
                 }
-                let num: String = chars[start..i].iter().collect();
-                out.colored(&num, theme.json_number);
-            }
-            't' | 'f' => {
-                let word: String = chars[i..].iter().take(5).collect();
-                if word.starts_with("true") {
-                    out.colored("true", theme.json_bool);
-                    i += 4;
-                } else if word.starts_with("false") {
-                    out.colored("false", theme.json_bool);
-                    i += 5;
-                } else {
-                    print!("{}", ch);
-                    i += 1;
-                }
+                out.colored(&json[start..i], theme.json_number);
             }
-            'n' => {
-                let word: String = chars[i..].iter().take(4).collect();
-                if word == "null" {
-                    out.colored("null", theme.json_null);
-                    i += 4;
-                } else {
-                    print!("{}", ch);
-                    i += 1;
-                }
+            b't' if bytes[i..].starts_with(b"true") => {
+                out.colored("true", theme.json_bool);
+                i += 4;
+            }
+            b'f' if bytes[i..].starts_with(b"false") => {
+                out.colored("false", theme.json_bool);
+                i += 5;
             }
-            '\n' => {
-                println!();
+            b'n' if bytes[i..].starts_with(b"null") => {
+                out.colored("null", theme.json_null);
+                i += 4;
+            }
+            b'\n' => {
+                out.newline();
                 after_colon = false;
                 i += 1;
             }
             _ => {
-                print!("{}", ch);
-                i += 1;
+                // Not an ASCII control byte we care about — step by this
+                // char's full UTF-8 width so multi-byte text (non-ASCII
+                // keys/values) never gets sliced mid-codepoint.
+                let len = json[i..].chars().next().map(char::len_utf8).unwrap_or(1);
+                out.raw(&json[i..i + len]);
+                i += len;
             }
         }
     }
 
     if !json.ends_with('\n') {
-        println!();
+        out.newline();
     }
 }
 
-fn rainbow_color(depth: usize) -> (u8, u8, u8) {
-    RAINBOW[depth % RAINBOW.len()]
-}
-
-fn is_likely_key(json: &str, quote_pos: usize) -> bool {
-    let chars: Vec<char> = json[quote_pos + 1..].chars().collect();
-    let mut i = 0;
-    let mut escaped = false;
-
-    while i < chars.len() {
-        if escaped {
-            escaped = false;
-            i += 1;
-            continue;
-        }
-        if chars[i] == '\\' {
-            escaped = true;
-            i += 1;
-            continue;
-        }
-        if chars[i] == '"' {
-            for j in (i + 1)..chars.len() {
-                if chars[j] == ':' {
-                    return true;
-                }
-                if !chars[j].is_whitespace() {
-                    return false;
-                }
+/// Scans forward from the opening quote at byte offset `start` to the
+/// matching closing quote (escaped quotes don't count), returning the
+/// index just past it. The closing quote must match whatever opened the
+/// string — `"` or, in JSON5's lenient mode, `'` — so a single-quoted
+/// string isn't cut short by a literal `"` inside it. Backslash/quote
+/// bytes are always distinguishable at the byte level even inside
+/// multi-byte UTF-8 text, since every byte of a multi-byte codepoint has
+/// its high bit set — so a plain `memchr2` over raw bytes is safe here
+/// without decoding.
+///
+/// Also reports whether this string looks like an object key: it isn't
+/// itself a value (`after_colon` is false) and, after optional whitespace,
+/// the next byte is `:`.
+fn scan_string(bytes: &[u8], start: usize, after_colon: bool) -> (usize, bool) {
+    let quote = bytes[start];
+    let mut i = start + 1;
+    loop {
+        match memchr2(quote, b'\\', &bytes[i..]) {
+            Some(off) if bytes[i + off] == b'\\' && i + off + 2 <= bytes.len() => i += off + 2,
+            Some(off) if bytes[i + off] == b'\\' => {
+                // Trailing lone backslash at EOF (an unterminated string) —
+                // nothing left to escape, so stop instead of overrunning.
+                i = bytes.len();
+                break;
+            }
+            Some(off) => {
+                i += off + 1;
+                break;
+            }
+            None => {
+                i = bytes.len();
+                break;
             }
-            return false;
         }
+    }
+
+    let is_key = !after_colon && is_followed_by_colon(bytes, i);
+    (i, is_key)
+}
+
+fn is_followed_by_colon(bytes: &[u8], mut i: usize) -> bool {
+    while i < bytes.len() && bytes[i].is_ascii_whitespace() {
         i += 1;
     }
-    false
+    bytes.get(i) == Some(&b':')
+}
+
+fn rainbow_color(depth: usize) -> (u8, u8, u8) {
+    RAINBOW[depth % RAINBOW.len()]
 }
@@ -1,5 +1,15 @@
 //! Markdown renderer - Claude Code inspired style
 //!
+//! Two-phase pipeline: `parse_document` turns the `pulldown_cmark` event
+//! stream into an `MdElement` tree (headings, paragraphs, lists, tables, ...
+//! with nested `Inline` spans), then `RenderCtx` walks that tree to produce
+//! output. The old design rendered straight off the event stream and
+//! committed output before seeing a whole block, so it couldn't reflow
+//! paragraphs to `content_width()` or size list/quote indentation against
+//! the terminal. Building the tree first gives the render pass that
+//! lookahead, and leaves a structured representation other subsystems could
+//! walk or snapshot-test.
+//!
 //! Design principles:
 //!   - Clean, minimal, readable
 //!   - Headings: `# ` prefix with bold color, no decorative underlines
@@ -8,483 +18,905 @@
 //!   - Blockquotes: thin `│` bar with dimmed text
 //!   - Tables: simple box-drawing borders
 
+use std::collections::HashMap;
+use std::iter::Peekable;
+
 use crossterm::style::Color;
 use pulldown_cmark::{
     Alignment, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd,
 };
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::output::Output;
 use crate::theme::Theme;
 
-pub fn render(content: &str, theme: &Theme, out: &Output) {
+/// Pastel rainbow for heading levels 1-6, cycling the same way
+/// `render::json::RAINBOW` cycles bracket-nesting depth.
+const HEADING_RAINBOW: &[(u8, u8, u8)] = &[
+    (245, 135, 135), // coral
+    (245, 183, 135), // orange
+    (235, 219, 137), // yellow
+    (163, 217, 145), // green
+    (137, 199, 219), // cyan
+    (163, 165, 245), // blue/violet
+];
+
+pub fn render(content: &str, theme: &Theme, out: &Output, toc: bool) {
     let options = Options::ENABLE_TABLES
         | Options::ENABLE_STRIKETHROUGH
         | Options::ENABLE_TASKLISTS
         | Options::ENABLE_FOOTNOTES;
 
     let parser = Parser::new_ext(content, options);
-    let mut ctx = RenderContext::new(theme, out);
-    ctx.process(parser);
-}
+    let doc = parse_document(parser);
 
-struct RenderContext<'a> {
-    theme: &'a Theme,
-    out: &'a Output,
+    let mut ctx = RenderCtx::new(theme, out);
+    ctx.render_blocks(&doc, &Indent::default());
+    out.newline();
 
-    in_heading: Option<u8>,
-    in_bold: bool,
-    in_italic: bool,
-    in_strike: bool,
-    in_code_block: bool,
-    in_block_quote: u32, // nesting depth
-    in_link: bool,
-    link_url: String,
-    code_lang: String,
-    code_buffer: String,
-    list_stack: Vec<ListState>,
-    need_newline: bool,
-    in_table: bool,
-    table_alignments: Vec<Alignment>,
-    table_row: Vec<String>,
-    table_rows: Vec<Vec<String>>,
-    table_is_header: bool,
-    in_table_cell: bool,
-    cell_buffer: String,
-    in_paragraph: bool,
+    if toc {
+        ctx.render_toc();
+    }
 }
 
+// ─── Document tree ────────────────────────────────────────────────
+
 #[derive(Clone)]
-enum ListState {
-    Ordered(u64),
-    Unordered,
+enum Inline {
+    Text(String),
+    Code(String),
+    Bold(Vec<Inline>),
+    Italic(Vec<Inline>),
+    Strike(Vec<Inline>),
+    Link { url: String, children: Vec<Inline> },
+    Image { url: String, alt: Vec<Inline> },
+    FootnoteRef(String),
+    SoftBreak,
+    HardBreak,
 }
 
-impl<'a> RenderContext<'a> {
-    fn new(theme: &'a Theme, out: &'a Output) -> Self {
-        Self {
-            theme,
-            out,
-            in_heading: None,
-            in_bold: false,
-            in_italic: false,
-            in_strike: false,
-            in_code_block: false,
-            in_block_quote: 0,
-            in_link: false,
-            link_url: String::new(),
-            code_lang: String::new(),
-            code_buffer: String::new(),
-            list_stack: Vec::new(),
-            need_newline: false,
-            in_table: false,
-            table_alignments: Vec::new(),
-            table_row: Vec::new(),
-            table_rows: Vec::new(),
-            table_is_header: false,
-            in_table_cell: false,
-            cell_buffer: String::new(),
-            in_paragraph: false,
-        }
-    }
-
-    fn process(&mut self, parser: Parser) {
-        for event in parser {
-            match event {
-                Event::Start(tag) => self.start_tag(tag),
-                Event::End(tag) => self.end_tag(tag),
-                Event::Text(text) => self.text(&text),
-                Event::Code(code) => self.inline_code(&code),
-                Event::SoftBreak => self.soft_break(),
-                Event::HardBreak => {
-                    println!();
-                    self.print_indent();
-                }
-                Event::Rule => self.rule(),
-                Event::TaskListMarker(checked) => self.task_marker(checked),
-                Event::FootnoteReference(name) => {
-                    self.out.colored("[", self.theme.link);
-                    self.out.bold_colored(&name, self.theme.link);
-                    self.out.colored("]", self.theme.link);
-                }
-                _ => {}
-            }
-        }
-        // Ensure final newline
-        println!();
-    }
+#[derive(Clone)]
+struct ListItem {
+    checked: Option<bool>,
+    content: Vec<MdElement>,
+}
 
-    // ─── Tag Start ────────────────────────────────────────────
+#[derive(Clone)]
+enum MdElement {
+    Heading {
+        level: u8,
+        content: Vec<Inline>,
+    },
+    Paragraph(Vec<Inline>),
+    List {
+        ordered: Option<u64>,
+        items: Vec<ListItem>,
+    },
+    BlockQuote(Vec<MdElement>),
+    CodeBlock {
+        lang: String,
+        code: String,
+    },
+    Table {
+        alignments: Vec<Alignment>,
+        header: Vec<Vec<Inline>>,
+        rows: Vec<Vec<Vec<Inline>>>,
+    },
+    Rule,
+}
 
-    fn start_tag(&mut self, tag: Tag) {
-        match tag {
-            Tag::Heading { level, .. } => {
-                let lvl = heading_level(level);
-                self.in_heading = Some(lvl);
-                self.ensure_blank_line();
+/// Recursive-descent builder over the flat `pulldown_cmark` event stream.
+/// Each `parse_*` function consumes events up to its matching `TagEnd`, so
+/// nesting in the resulting tree mirrors nesting in the source directly,
+/// rather than being reconstructed from a running stack of flags.
+fn parse_document(parser: Parser) -> Vec<MdElement> {
+    let mut events = parser.peekable();
+    parse_blocks(&mut events, None)
+}
 
-                // Print `# `, `## `, etc. prefix
-                let color = self.heading_color(lvl);
-                let prefix = "#".repeat(lvl as usize);
-                self.out.bold_colored(&prefix, color);
-                self.out.bold_colored(" ", color);
+fn parse_blocks<'a>(
+    events: &mut Peekable<Parser<'a>>,
+    until: Option<TagEnd>,
+) -> Vec<MdElement> {
+    let mut blocks = Vec::new();
+    while let Some(event) = events.next() {
+        match event {
+            Event::End(tag) if Some(tag) == until => return blocks,
+            Event::Start(Tag::Heading { level, .. }) => {
+                let content = parse_inlines(events, TagEnd::Heading(level));
+                blocks.push(MdElement::Heading {
+                    level: heading_level(level),
+                    content,
+                });
             }
-
-            Tag::Paragraph => {
-                if self.in_block_quote > 0 {
-                    self.print_quote_bar();
-                } else if self.list_stack.is_empty() {
-                    self.ensure_blank_line();
-                }
-                self.in_paragraph = true;
+            Event::Start(Tag::Paragraph) => {
+                let content = parse_inlines(events, TagEnd::Paragraph);
+                blocks.push(MdElement::Paragraph(content));
             }
-
-            Tag::BlockQuote => {
-                self.in_block_quote += 1;
-                if self.in_block_quote == 1 {
-                    self.ensure_blank_line();
-                }
+            Event::Start(Tag::BlockQuote) => {
+                let content = parse_blocks(events, Some(TagEnd::BlockQuote));
+                blocks.push(MdElement::BlockQuote(content));
             }
-
-            Tag::CodeBlock(kind) => {
-                self.in_code_block = true;
-                self.code_buffer.clear();
-                self.code_lang = match &kind {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let lang = match &kind {
                     CodeBlockKind::Fenced(lang) => {
-                        let l = lang.split_whitespace().next().unwrap_or("").to_string();
-                        l
+                        lang.split_whitespace().next().unwrap_or("").to_string()
                     }
-                    _ => String::new(),
+                    CodeBlockKind::Indented => String::new(),
                 };
-                self.ensure_blank_line();
-            }
-
-            Tag::List(start) => {
-                if self.list_stack.is_empty() {
-                    self.ensure_blank_line();
-                }
-                match start {
-                    Some(n) => self.list_stack.push(ListState::Ordered(n)),
-                    None => self.list_stack.push(ListState::Unordered),
+                let mut code = String::new();
+                for event in events.by_ref() {
+                    match event {
+                        Event::Text(t) => code.push_str(&t),
+                        Event::End(TagEnd::CodeBlock) => break,
+                        _ => {}
+                    }
                 }
+                blocks.push(MdElement::CodeBlock { lang, code });
             }
+            Event::Start(Tag::List(start)) => {
+                let items = parse_list_items(events);
+                blocks.push(MdElement::List { ordered: start, items });
+            }
+            Event::Start(Tag::Table(alignments)) => {
+                let (header, rows) = parse_table(events);
+                blocks.push(MdElement::Table { alignments, header, rows });
+            }
+            Event::Rule => blocks.push(MdElement::Rule),
+            Event::FootnoteReference(name) => {
+                blocks.push(MdElement::Paragraph(vec![Inline::FootnoteRef(name.to_string())]));
+            }
+            _ => {}
+        }
+    }
+    blocks
+}
 
-            Tag::Item => {
-                if self.need_newline {
-                    println!();
-                }
-                self.print_list_indent();
-
-                if let Some(state) = self.list_stack.last_mut() {
-                    match state {
-                        ListState::Ordered(n) => {
-                            self.out.colored(&format!("{}. ", n), self.theme.list_bullet);
-                            *n += 1;
-                        }
-                        ListState::Unordered => {
-                            self.out.colored("- ", self.theme.list_bullet);
-                        }
+fn parse_list_items<'a>(events: &mut Peekable<Parser<'a>>) -> Vec<ListItem> {
+    let mut items = Vec::new();
+    while let Some(event) = events.next() {
+        match event {
+            Event::End(TagEnd::List(_)) => break,
+            Event::Start(Tag::Item) => {
+                let mut checked = None;
+                if matches!(events.peek(), Some(Event::TaskListMarker(_))) {
+                    if let Some(Event::TaskListMarker(c)) = events.next() {
+                        checked = Some(c);
                     }
                 }
+                let content = parse_blocks(events, Some(TagEnd::Item));
+                items.push(ListItem { checked, content });
             }
+            _ => {}
+        }
+    }
+    items
+}
 
-            Tag::Emphasis => self.in_italic = true,
-            Tag::Strong => self.in_bold = true,
-            Tag::Strikethrough => self.in_strike = true,
-
-            Tag::Link { dest_url, .. } => {
-                self.in_link = true;
-                self.link_url = dest_url.to_string();
-                self.out.hyperlink_start(&self.link_url);
+fn parse_table<'a>(
+    events: &mut Peekable<Parser<'a>>,
+) -> (Vec<Vec<Inline>>, Vec<Vec<Vec<Inline>>>) {
+    let mut header = Vec::new();
+    let mut rows = Vec::new();
+    let mut in_header = false;
+    while let Some(event) = events.next() {
+        match event {
+            Event::End(TagEnd::Table) => break,
+            Event::Start(Tag::TableHead) => in_header = true,
+            Event::End(TagEnd::TableHead) => in_header = false,
+            Event::Start(Tag::TableRow) => {
+                let row = parse_table_row(events);
+                if in_header {
+                    header = row;
+                } else {
+                    rows.push(row);
+                }
             }
+            _ => {}
+        }
+    }
+    (header, rows)
+}
 
-            Tag::Image { dest_url, .. } => {
-                self.in_link = true;
-                self.link_url = dest_url.to_string();
-                self.out.colored("[image: ", self.theme.link_url);
+fn parse_table_row<'a>(events: &mut Peekable<Parser<'a>>) -> Vec<Vec<Inline>> {
+    let mut cells = Vec::new();
+    while let Some(event) = events.next() {
+        match event {
+            Event::End(TagEnd::TableRow) => break,
+            Event::Start(Tag::TableCell) => {
+                cells.push(parse_inlines(events, TagEnd::TableCell));
             }
+            _ => {}
+        }
+    }
+    cells
+}
 
-            Tag::Table(alignments) => {
-                self.in_table = true;
-                self.table_alignments = alignments;
-                self.table_rows.clear();
-                self.ensure_blank_line();
+fn parse_inlines<'a>(events: &mut Peekable<Parser<'a>>, until: TagEnd) -> Vec<Inline> {
+    let mut spans = Vec::new();
+    while let Some(event) = events.next() {
+        match event {
+            Event::End(tag) if tag == until => break,
+            Event::Text(t) => spans.extend(split_bare_urls(&t)),
+            Event::Code(c) => spans.push(Inline::Code(c.to_string())),
+            Event::SoftBreak => spans.push(Inline::SoftBreak),
+            Event::HardBreak => spans.push(Inline::HardBreak),
+            Event::FootnoteReference(name) => spans.push(Inline::FootnoteRef(name.to_string())),
+            Event::Start(Tag::Emphasis) => {
+                spans.push(Inline::Italic(parse_inlines(events, TagEnd::Emphasis)))
             }
-
-            Tag::TableHead => {
-                self.table_is_header = true;
-                self.table_row = Vec::new();
+            Event::Start(Tag::Strong) => {
+                spans.push(Inline::Bold(parse_inlines(events, TagEnd::Strong)))
             }
-
-            Tag::TableRow => {
-                self.table_row = Vec::new();
+            Event::Start(Tag::Strikethrough) => {
+                spans.push(Inline::Strike(parse_inlines(events, TagEnd::Strikethrough)))
             }
-
-            Tag::TableCell => {
-                self.in_table_cell = true;
-                self.cell_buffer.clear();
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                let children = parse_inlines(events, TagEnd::Link);
+                spans.push(Inline::Link { url: dest_url.to_string(), children });
+            }
+            Event::Start(Tag::Image { dest_url, .. }) => {
+                let alt = parse_inlines(events, TagEnd::Image);
+                spans.push(Inline::Image { url: dest_url.to_string(), alt });
             }
-
             _ => {}
         }
     }
+    spans
+}
 
-    // ─── Tag End ──────────────────────────────────────────────
+/// Splits a run of plain text on bare `http://`/`https://` URLs, turning
+/// each into an `Inline::Link` whose visible text is the URL itself — so
+/// a paragraph doesn't need `[text](url)` syntax for a link to render as
+/// one. Trailing sentence punctuation (`.`, `,`, `;`, `:`, `!`, `?`) is
+/// excluded from the URL so "see https://example.com." doesn't swallow
+/// the period.
+fn split_bare_urls(text: &str) -> Vec<Inline> {
+    let mut spans = Vec::new();
+    let mut rest = text;
+    while let Some(start) = find_url_start(rest) {
+        if start > 0 {
+            spans.push(Inline::Text(rest[..start].to_string()));
+        }
+        let len = url_len(&rest[start..]);
+        let url = rest[start..start + len].to_string();
+        spans.push(Inline::Link { url: url.clone(), children: vec![Inline::Text(url)] });
+        rest = &rest[start + len..];
+    }
+    if !rest.is_empty() {
+        spans.push(Inline::Text(rest.to_string()));
+    }
+    spans
+}
 
-    fn end_tag(&mut self, tag: TagEnd) {
-        match tag {
-            TagEnd::Heading(_) => {
-                println!();
-                self.in_heading = None;
-                self.need_newline = true;
-            }
+fn find_url_start(text: &str) -> Option<usize> {
+    text.find("https://").into_iter().chain(text.find("http://")).min()
+}
 
-            TagEnd::Paragraph => {
-                println!();
-                self.in_paragraph = false;
-                self.need_newline = true;
-            }
+fn url_len(text: &str) -> usize {
+    let mut end = text
+        .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | ')' | ']' | '"' | '\''))
+        .unwrap_or(text.len());
+    while end > 0 && matches!(text.as_bytes()[end - 1], b'.' | b',' | b';' | b':' | b'!' | b'?') {
+        end -= 1;
+    }
+    end
+}
 
-            TagEnd::BlockQuote => {
-                self.in_block_quote = self.in_block_quote.saturating_sub(1);
-                self.need_newline = true;
-            }
+fn heading_level(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
 
-            TagEnd::CodeBlock => {
-                self.render_code_block();
-                self.in_code_block = false;
-                self.code_lang.clear();
-                self.code_buffer.clear();
-                self.need_newline = true;
+/// Recursively gathers the plain text of `inlines`, turning soft/hard
+/// breaks into spaces and wrapping inline code in backticks so headings,
+/// table cells, and TOC entries get a readable flat string.
+fn collect_text(inlines: &[Inline], out: &mut String) {
+    for inline in inlines {
+        match inline {
+            Inline::Text(t) => out.push_str(t),
+            Inline::Code(c) => {
+                out.push('`');
+                out.push_str(c);
+                out.push('`');
             }
-
-            TagEnd::List(_) => {
-                self.list_stack.pop();
-                if self.list_stack.is_empty() {
-                    self.need_newline = true;
-                }
+            Inline::Bold(c) | Inline::Italic(c) | Inline::Strike(c) => collect_text(c, out),
+            Inline::Link { children, .. } => collect_text(children, out),
+            Inline::Image { alt, .. } => collect_text(alt, out),
+            Inline::FootnoteRef(name) => {
+                out.push('[');
+                out.push_str(name);
+                out.push(']');
             }
+            Inline::SoftBreak | Inline::HardBreak => out.push(' '),
+        }
+    }
+}
 
-            TagEnd::Item => {
-                println!();
-                self.need_newline = false;
-            }
+fn plain_text(inlines: &[Inline]) -> String {
+    let mut s = String::new();
+    collect_text(inlines, &mut s);
+    s
+}
 
-            TagEnd::Emphasis => self.in_italic = false,
-            TagEnd::Strong => self.in_bold = false,
-            TagEnd::Strikethrough => self.in_strike = false,
+// ─── Inline word-wrapping ──────────────────────────────────────────
+
+#[derive(Clone, Default)]
+struct InlineStyle {
+    bold: bool,
+    italic: bool,
+    strike: bool,
+    code: bool,
+    link: bool,
+    /// Marks a synthetic `(url)` word appended after a link's visible
+    /// text, rendered dim rather than underlined.
+    dim_suffix: bool,
+}
 
-            TagEnd::Link => {
-                self.out.hyperlink_end();
-                self.in_link = false;
-                self.link_url.clear();
-            }
+#[derive(Clone)]
+struct Word {
+    text: String,
+    style: InlineStyle,
+}
+
+enum FlatToken {
+    Word(Word),
+    Break,
+}
 
-            TagEnd::Image => {
-                if !self.link_url.is_empty() {
-                    self.out.dim(&self.link_url, self.theme.link_url);
+fn flatten_inline(inlines: &[Inline], style: InlineStyle, out: &mut Vec<FlatToken>) {
+    for inline in inlines {
+        match inline {
+            Inline::Text(t) => push_words(t, &style, out),
+            Inline::Code(c) => {
+                if !c.trim().is_empty() {
+                    out.push(FlatToken::Word(Word {
+                        text: c.clone(),
+                        style: InlineStyle { code: true, ..style.clone() },
+                    }));
                 }
-                self.out.colored("]", self.theme.link_url);
-                self.in_link = false;
-                self.link_url.clear();
             }
-
-            TagEnd::Table => {
-                self.render_table();
-                self.in_table = false;
-                self.table_rows.clear();
-                self.need_newline = true;
+            Inline::Bold(c) => flatten_inline(c, InlineStyle { bold: true, ..style.clone() }, out),
+            Inline::Italic(c) => {
+                flatten_inline(c, InlineStyle { italic: true, ..style.clone() }, out)
             }
-
-            TagEnd::TableHead => {
-                self.table_rows.push(self.table_row.clone());
-                self.table_is_header = false;
+            Inline::Strike(c) => {
+                flatten_inline(c, InlineStyle { strike: true, ..style.clone() }, out)
             }
+            Inline::Link { url, children } => {
+                flatten_inline(children, InlineStyle { link: true, ..style.clone() }, out);
+                // Autolinks render the URL as their own visible text, so
+                // appending it again as a "(url)" suffix would just repeat it.
+                if !url.is_empty() && plain_text(children) != *url {
+                    out.push(FlatToken::Word(Word {
+                        text: format!("({})", url),
+                        style: InlineStyle { dim_suffix: true, ..InlineStyle::default() },
+                    }));
+                }
+            }
+            Inline::Image { alt, .. } => {
+                out.push(FlatToken::Word(Word { text: "[image:".to_string(), style: style.clone() }));
+                flatten_inline(alt, style.clone(), out);
+                out.push(FlatToken::Word(Word { text: "]".to_string(), style }));
+            }
+            Inline::FootnoteRef(name) => out.push(FlatToken::Word(Word {
+                text: format!("[{}]", name),
+                style: style.clone(),
+            })),
+            Inline::SoftBreak => {}
+            Inline::HardBreak => out.push(FlatToken::Break),
+        }
+    }
+}
+
+fn push_words(text: &str, style: &InlineStyle, out: &mut Vec<FlatToken>) {
+    for word in text.split_whitespace() {
+        out.push(FlatToken::Word(Word { text: word.to_string(), style: style.clone() }));
+    }
+}
 
-            TagEnd::TableRow => {
-                self.table_rows.push(self.table_row.clone());
+/// Greedily packs `tokens` into lines no wider than `width`, hard-breaking
+/// any single word that alone exceeds `width` and starting a fresh line at
+/// every explicit `FlatToken::Break` (a markdown hard break).
+fn wrap_tokens(tokens: &[FlatToken], width: usize) -> Vec<Vec<Word>> {
+    let width = width.max(1);
+    let mut lines: Vec<Vec<Word>> = Vec::new();
+    let mut current: Vec<Word> = Vec::new();
+    let mut current_width = 0usize;
+
+    for token in tokens {
+        match token {
+            FlatToken::Break => {
+                lines.push(std::mem::take(&mut current));
+                current_width = 0;
             }
+            FlatToken::Word(w) => {
+                let word_width = w.text.width();
 
-            TagEnd::TableCell => {
-                self.in_table_cell = false;
-                self.table_row.push(self.cell_buffer.clone());
+                if word_width > width {
+                    if !current.is_empty() {
+                        lines.push(std::mem::take(&mut current));
+                        current_width = 0;
+                    }
+                    for chunk in hard_break_word(&w.text, width) {
+                        lines.push(vec![Word { text: chunk, style: w.style.clone() }]);
+                    }
+                    continue;
+                }
+
+                let sep_width = if current.is_empty() { 0 } else { 1 };
+                if current_width + sep_width + word_width > width {
+                    lines.push(std::mem::take(&mut current));
+                    current_width = 0;
+                } else if !current.is_empty() {
+                    current_width += 1;
+                }
+                current.push(w.clone());
+                current_width += word_width;
             }
+        }
+    }
 
-            _ => {}
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Breaks a single overlong token into `width`-wide chunks, used for both
+/// paragraph reflow and table cell wrapping.
+fn hard_break_word(word: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut chunks = Vec::new();
+    let mut chunk = String::new();
+    let mut chunk_width = 0usize;
+    for ch in word.chars() {
+        let cw = ch.width().unwrap_or(0);
+        if chunk_width + cw > width && !chunk.is_empty() {
+            chunks.push(std::mem::take(&mut chunk));
+            chunk_width = 0;
         }
+        chunk.push(ch);
+        chunk_width += cw;
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
     }
+    chunks
+}
 
-    // ─── Text Content ─────────────────────────────────────────
+/// Splits a syntax-highlighted code line into rows of at most `width`
+/// display columns, breaking at character boundaries (not words) since
+/// code shouldn't be reflowed — it should only spill onto a continuation
+/// row when it's genuinely too long for the panel.
+fn wrap_colored_chars(chars: &[(Color, char)], width: usize) -> Vec<Vec<(Color, char)>> {
+    let width = width.max(1);
+    if chars.is_empty() {
+        return vec![Vec::new()];
+    }
 
-    fn text(&mut self, text: &str) {
-        if self.in_table_cell {
-            self.cell_buffer.push_str(text);
-            return;
+    let mut rows = Vec::new();
+    let mut current: Vec<(Color, char)> = Vec::new();
+    let mut current_width = 0usize;
+
+    for &(color, ch) in chars {
+        let cw = ch.width().unwrap_or(0);
+        if current_width + cw > width && !current.is_empty() {
+            rows.push(std::mem::take(&mut current));
+            current_width = 0;
         }
+        current.push((color, ch));
+        current_width += cw;
+    }
+    if !current.is_empty() {
+        rows.push(current);
+    }
+    rows
+}
 
-        if self.in_code_block {
-            self.code_buffer.push_str(text);
-            return;
+/// Word-wraps `text` into physical lines no wider than `width` columns,
+/// greedily packing whitespace-separated words and hard-breaking any single
+/// token that alone exceeds `width` (e.g. a long URL or identifier).
+fn wrap_cell(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0usize;
+
+    for word in text.split_whitespace() {
+        let word_width = word.width();
+
+        if word_width > width {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+                current_width = 0;
+            }
+            let chunks = hard_break_word(word, width);
+            let last = chunks.len().saturating_sub(1);
+            for (i, chunk) in chunks.into_iter().enumerate() {
+                if i == last {
+                    current_width = chunk.width();
+                    current = chunk;
+                } else {
+                    lines.push(chunk);
+                }
+            }
+            continue;
         }
 
-        // Block quote: render inline (bar printed at paragraph start)
-        if self.in_block_quote > 0 && self.in_heading.is_none() {
-            self.out.italic_colored(text, self.theme.quote);
-            return;
+        let sep_width = if current.is_empty() { 0 } else { 1 };
+        if current_width + sep_width + word_width > width {
+            lines.push(std::mem::take(&mut current));
+            current_width = 0;
+        }
+        if !current.is_empty() {
+            current.push(' ');
+            current_width += 1;
         }
+        current.push_str(word);
+        current_width += word_width;
+    }
 
-        let color = if let Some(lvl) = self.in_heading {
-            self.heading_color(lvl)
-        } else if self.in_link {
-            self.theme.link
-        } else {
-            self.theme.text
-        };
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
 
-        if self.in_heading.is_some() {
-            self.out.bold_colored(text, color);
-        } else if self.in_bold && self.in_italic {
-            self.out.bold_colored(text, self.theme.bold);
-        } else if self.in_bold {
-            self.out.bold_colored(text, self.theme.bold);
-        } else if self.in_italic {
-            self.out.italic_colored(text, color);
-        } else if self.in_strike {
-            self.out.strike_colored(text, self.theme.strike);
-        } else if self.in_link {
-            self.out.underline_colored(text, color);
-        } else {
-            self.out.colored(text, color);
+    lines
+}
+
+// ─── Indentation ────────────────────────────────────────────────────
+
+/// Accumulated left margin for nested lists/blockquotes, built up as the
+/// render pass descends into the tree so every wrapped continuation line
+/// re-prints the same prefix as the line that opened the block.
+#[derive(Clone, Default)]
+struct Indent {
+    segs: Vec<IndentSeg>,
+    /// Once inside a list item or blockquote, sibling blocks pack tightly
+    /// (no blank separator line), matching the rest of this renderer's
+    /// "tight list" behavior.
+    suppress_blank: bool,
+}
+
+#[derive(Clone)]
+enum IndentSeg {
+    Quote,
+    Spaces(usize),
+}
+
+impl IndentSeg {
+    fn width(&self) -> usize {
+        match self {
+            IndentSeg::Quote => 2,
+            IndentSeg::Spaces(n) => *n,
         }
     }
+}
 
-    fn inline_code(&mut self, code: &str) {
-        if self.in_table_cell {
-            self.cell_buffer.push('`');
-            self.cell_buffer.push_str(code);
-            self.cell_buffer.push('`');
-            return;
+impl Indent {
+    fn width(&self) -> usize {
+        self.segs.iter().map(IndentSeg::width).sum()
+    }
+
+    fn push_quote(&self) -> Indent {
+        let mut next = self.clone();
+        next.segs.push(IndentSeg::Quote);
+        next.suppress_blank = true;
+        next
+    }
+
+    fn push_spaces(&self, n: usize) -> Indent {
+        let mut next = self.clone();
+        next.segs.push(IndentSeg::Spaces(n));
+        next.suppress_blank = true;
+        next
+    }
+}
+
+// ─── Rendering ──────────────────────────────────────────────────────
+
+struct RenderCtx<'a> {
+    theme: &'a Theme,
+    out: &'a Output,
+    need_blank: bool,
+    toc_headings: Vec<(u8, String, String)>,
+    anchor_counts: HashMap<String, u32>,
+}
+
+impl<'a> RenderCtx<'a> {
+    fn new(theme: &'a Theme, out: &'a Output) -> Self {
+        Self {
+            theme,
+            out,
+            need_blank: false,
+            toc_headings: Vec::new(),
+            anchor_counts: HashMap::new(),
         }
+    }
 
-        // ` code ` with background
-        self.out.colored_bg(
-            &format!(" {} ", code),
-            self.theme.code_fg,
-            self.theme.code_bg,
-        );
+    fn render_blocks(&mut self, blocks: &[MdElement], indent: &Indent) {
+        for block in blocks {
+            self.render_block(block, indent);
+        }
     }
 
-    fn soft_break(&mut self) {
-        if self.in_table_cell {
-            self.cell_buffer.push(' ');
-            return;
+    fn render_block(&mut self, block: &MdElement, indent: &Indent) {
+        match block {
+            MdElement::Heading { level, content } => {
+                self.ensure_blank_line();
+                self.print_indent(indent);
+                let color = self.heading_color(*level);
+                let prefix = "#".repeat(*level as usize);
+                self.out.bold_colored(&prefix, color);
+                self.out.bold_colored(" ", color);
+
+                let text = plain_text(content);
+                let anchor = self.slugify_anchor(&text);
+                self.toc_headings.push((*level, text.clone(), anchor));
+                self.out.bold_colored(&text, color);
+                self.out.newline();
+                self.need_blank = true;
+            }
+
+            MdElement::Paragraph(content) => {
+                if !indent.suppress_blank {
+                    self.ensure_blank_line();
+                }
+                self.render_paragraph(content, indent);
+                self.need_blank = true;
+            }
+
+            MdElement::List { ordered, items } => {
+                if !indent.suppress_blank {
+                    self.ensure_blank_line();
+                }
+                self.render_list(ordered, items, indent);
+                self.need_blank = true;
+            }
+
+            MdElement::BlockQuote(content) => {
+                if !indent.suppress_blank {
+                    self.ensure_blank_line();
+                }
+                let inner = indent.push_quote();
+                self.render_blocks(content, &inner);
+                self.need_blank = true;
+            }
+
+            MdElement::CodeBlock { lang, code } => {
+                self.ensure_blank_line();
+                self.render_code_block(lang, code, indent);
+                self.need_blank = true;
+            }
+
+            MdElement::Table { alignments, header, rows } => {
+                self.ensure_blank_line();
+                self.render_table(alignments, header, rows, indent);
+                self.need_blank = true;
+            }
+
+            MdElement::Rule => {
+                self.ensure_blank_line();
+                self.print_indent(indent);
+                let width = self.content_width(indent).min(60);
+                self.out.colored(&"─".repeat(width), self.theme.hr);
+                self.out.newline();
+                self.need_blank = true;
+            }
         }
-        if self.in_code_block {
-            self.code_buffer.push('\n');
-            return;
+    }
+
+    // ─── Paragraphs ───────────────────────────────────────────
+
+    fn render_paragraph(&mut self, content: &[Inline], indent: &Indent) {
+        let mut tokens = Vec::new();
+        flatten_inline(content, InlineStyle::default(), &mut tokens);
+        let width = self.content_width(indent);
+        let lines = wrap_tokens(&tokens, width);
+        for words in &lines {
+            self.print_indent(indent);
+            self.render_words(words);
+            self.out.newline();
         }
-        if self.in_block_quote > 0 {
-            println!();
-            self.print_quote_bar();
+    }
+
+    fn render_words(&self, words: &[Word]) {
+        for (i, word) in words.iter().enumerate() {
+            if i > 0 {
+                self.out.raw(" ");
+            }
+            self.render_word(word);
+        }
+    }
+
+    fn render_word(&self, word: &Word) {
+        let style = &word.style;
+        if style.dim_suffix {
+            self.out.dim(&word.text, self.theme.link_url);
+        } else if style.code {
+            self.out.colored_bg(
+                &format!(" {} ", word.text),
+                self.theme.code_fg,
+                self.theme.code_bg,
+            );
+        } else if style.bold {
+            self.out.bold_colored(&word.text, self.theme.bold);
+        } else if style.italic && style.link {
+            self.out.italic_colored(&word.text, self.theme.link);
+        } else if style.italic {
+            self.out.italic_colored(&word.text, self.theme.text);
+        } else if style.strike {
+            self.out.strike_colored(&word.text, self.theme.strike);
+        } else if style.link {
+            self.out.underline_colored(&word.text, self.theme.link);
         } else {
-            println!();
+            self.out.colored(&word.text, self.theme.text);
         }
     }
 
-    fn rule(&mut self) {
-        self.ensure_blank_line();
-        let width = self.content_width().min(60);
-        self.out.colored(&"─".repeat(width), self.theme.hr);
-        println!();
-        self.need_newline = true;
+    // ─── Lists ────────────────────────────────────────────────
+
+    fn render_list(&mut self, ordered: &Option<u64>, items: &[ListItem], indent: &Indent) {
+        let mut counter = ordered.unwrap_or(1);
+        for item in items {
+            let marker = match ordered {
+                Some(_) => format!("{}. ", counter),
+                None => "- ".to_string(),
+            };
+            counter += 1;
+            self.render_item(&marker, item.checked, &item.content, indent);
+        }
     }
 
-    fn task_marker(&mut self, checked: bool) {
-        if checked {
-            self.out.colored("[✓] ", self.theme.alert_tip);
-        } else {
-            self.out.colored("[ ] ", self.theme.hr);
+    fn render_item(
+        &mut self,
+        marker: &str,
+        checked: Option<bool>,
+        content: &[MdElement],
+        indent: &Indent,
+    ) {
+        self.print_indent(indent);
+        self.out.colored(marker, self.theme.list_bullet);
+        let mut marker_width = marker.width();
+
+        if let Some(checked) = checked {
+            let (cb, color) = if checked {
+                ("[✓] ", self.theme.alert_tip)
+            } else {
+                ("[ ] ", self.theme.hr)
+            };
+            self.out.colored(cb, color);
+            marker_width += cb.width();
+        }
+
+        let item_indent = indent.push_spaces(marker_width);
+
+        match content.first() {
+            Some(MdElement::Paragraph(inlines)) => {
+                let mut tokens = Vec::new();
+                flatten_inline(inlines, InlineStyle::default(), &mut tokens);
+                let width = self.content_width(&item_indent);
+                let lines = wrap_tokens(&tokens, width);
+                for (i, words) in lines.iter().enumerate() {
+                    if i > 0 {
+                        self.print_indent(&item_indent);
+                    }
+                    self.render_words(words);
+                    self.out.newline();
+                }
+                for block in &content[1..] {
+                    self.render_block(block, &item_indent);
+                }
+            }
+            Some(_) => {
+                self.out.newline();
+                self.render_blocks(content, &item_indent);
+            }
+            None => {
+                self.out.newline();
+            }
         }
     }
 
-    // ─── Code Block Rendering ─────────────────────────────────
+    // ─── Code blocks ──────────────────────────────────────────
 
-    fn render_code_block(&self) {
-        let content = &self.code_buffer;
-        let width = self.content_width();
+    fn render_code_block(&self, lang: &str, code: &str, indent: &Indent) {
+        let width = self.content_width(indent);
         let bg = self.theme.code_block_bg;
+        let (bg_r, bg_g, bg_b) = as_rgb(bg, (30, 30, 46));
 
-        let (bg_r, bg_g, bg_b) = if let Color::Rgb { r, g, b } = bg {
-            (r, g, b)
-        } else {
-            (30, 30, 46) // fallback dark bg
-        };
-
-        if !self.code_lang.is_empty() {
-            self.out.dim(&format!("  {}", self.code_lang), self.theme.code_lang);
-            println!();
+        if !lang.is_empty() {
+            self.print_indent(indent);
+            self.out.dim(&format!("  {}", lang), self.theme.code_lang);
+            self.out.newline();
         }
 
-        let highlighted = if !self.code_lang.is_empty() {
-            self.highlight_code(content, &self.code_lang)
+        let highlighted = if !lang.is_empty() {
+            self.highlight_code(code, lang)
         } else {
             None
         };
 
-        let (def_r, def_g, def_b) = if let Color::Rgb { r, g, b } = self.theme.code_block_fg {
-            (r, g, b)
-        } else {
-            (205, 214, 244)
-        };
+        let (def_r, def_g, def_b) = as_rgb(self.theme.code_block_fg, (205, 214, 244));
+        let default_color = Color::Rgb { r: def_r, g: def_g, b: def_b };
 
-        // Raw ANSI only — crossterm styled() resets bg between fragments
-        let lines: Vec<&str> = content.lines().collect();
+        let lines: Vec<&str> = code.lines().collect();
         for (i, line) in lines.iter().enumerate() {
-            print!("  \x1b[48;2;{};{};{}m", bg_r, bg_g, bg_b);
-
-            if let Some(ref hl_lines) = highlighted {
-                if i < hl_lines.len() {
-                    print!(" ");
-                    for (fg, text) in &hl_lines[i] {
-                        // Set fg+bg together, no reset between fragments
-                        if let Color::Rgb { r, g, b } = fg {
-                            print!("\x1b[38;2;{};{};{}m{}", r, g, b, text);
-                        } else {
-                            print!("{}", text);
-                        }
-                    }
-                } else {
-                    print!("\x1b[38;2;{};{};{}m {}", def_r, def_g, def_b, line);
-                }
+            let fragments: &[(Color, String)] = match &highlighted {
+                Some(hl_lines) if i < hl_lines.len() => &hl_lines[i],
+                _ => &[],
+            };
+            if fragments.is_empty() {
+                self.render_code_line(&[(default_color, (*line).to_string())], width, bg_r, bg_g, bg_b, indent);
+            } else {
+                self.render_code_line(fragments, width, bg_r, bg_g, bg_b, indent);
+            }
+        }
+    }
+
+    /// Prints one logical code line, soft-wrapping it across as many rows of
+    /// `width` columns as it needs. Continuation rows replace the usual
+    /// leading pad with a dim `↪ ` gutter and resume with whichever syntect
+    /// foreground color was active at the break, so a long string or
+    /// comment doesn't reset to the default hue mid-token.
+    fn render_code_line(
+        &self,
+        fragments: &[(Color, String)],
+        width: usize,
+        bg_r: u8,
+        bg_g: u8,
+        bg_b: u8,
+        indent: &Indent,
+    ) {
+        let chars: Vec<(Color, char)> = fragments
+            .iter()
+            .flat_map(|(c, t)| t.chars().map(move |ch| (*c, ch)))
+            .collect();
+
+        let content_width = width.saturating_sub(2).max(1);
+        let rows = wrap_colored_chars(&chars, content_width);
+
+        for (row_idx, row) in rows.iter().enumerate() {
+            self.print_indent(indent);
+            self.out.raw(&format!("  {}", self.out.ansi_bg(bg_r, bg_g, bg_b)));
+
+            let mut visible = 2usize;
+            if row_idx == 0 {
+                self.out.raw("  ");
             } else {
-                print!("\x1b[38;2;{};{};{}m {}", def_r, def_g, def_b, line);
+                self.out.dim("↪", self.theme.code_lang);
+                self.out.raw(" ");
             }
 
-            let visible_len = line.len() + 1;
-            if visible_len < width {
-                for _ in 0..(width - visible_len) {
-                    print!(" ");
+            let mut last_color: Option<Color> = None;
+            for (color, ch) in row {
+                if last_color != Some(*color) {
+                    if let Color::Rgb { r, g, b } = color {
+                        self.out.raw(&self.out.ansi_fg(*r, *g, *b));
+                    }
+                    last_color = Some(*color);
                 }
+                self.out.raw(&ch.to_string());
+                visible += ch.width().unwrap_or(0);
             }
-            print!(" \x1b[0m");
-            println!();
+
+            if visible < width {
+                self.out.raw(&" ".repeat(width - visible));
+            }
+            self.out.raw(" \x1b[0m");
+            self.out.newline();
         }
     }
 
     fn highlight_code(&self, code: &str, lang: &str) -> Option<Vec<Vec<(Color, String)>>> {
-        let ss = syntect::parsing::SyntaxSet::load_defaults_newlines();
-        let ts = syntect::highlighting::ThemeSet::load_defaults();
-
-        let syntax = ss
-            .find_syntax_by_token(lang)
-            .or_else(|| ss.find_syntax_by_extension(lang))
-            .or_else(|| ss.find_syntax_by_name(lang))
-            .or_else(|| {
-                // Fallback for unsupported languages (e.g. TypeScript → JavaScript)
-                let fb = crate::detect::syntax_fallback(lang);
-                ss.find_syntax_by_name(fb)
-                    .or_else(|| ss.find_syntax_by_token(fb))
-            })?;
+        let ts = crate::render::syntax::theme_set();
+        let (ss, syntax) = crate::render::syntax::find_syntax(lang);
 
         let st = ts
             .themes
@@ -496,7 +928,7 @@ impl<'a> RenderContext<'a> {
         let mut result = Vec::new();
 
         for line in syntect::util::LinesWithEndings::from(code) {
-            if let Ok(ranges) = h.highlight_line(line, &ss) {
+            if let Ok(ranges) = h.highlight_line(line, ss) {
                 let fragments: Vec<(Color, String)> = ranges
                     .iter()
                     .map(|(style, text)| {
@@ -509,7 +941,9 @@ impl<'a> RenderContext<'a> {
                         let fg_bright = (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000;
                         let bg_bright = if let Color::Rgb { r: br, g: bg, b: bb } = self.theme.code_block_bg {
                             (br as u32 * 299 + bg as u32 * 587 + bb as u32 * 114) / 1000
-                        } else { 35 };
+                        } else {
+                            35
+                        };
                         let contrast = if fg_bright > bg_bright {
                             fg_bright - bg_bright
                         } else {
@@ -517,9 +951,13 @@ impl<'a> RenderContext<'a> {
                         };
                         if contrast < 60 {
                             if let Color::Rgb { r: dr, g: dg, b: db } = self.theme.code_block_fg {
-                                r = dr; g = dg; b = db;
+                                r = dr;
+                                g = dg;
+                                b = db;
                             } else {
-                                r = 205; g = 214; b = 244;
+                                r = 205;
+                                g = 214;
+                                b = 244;
                             }
                         }
                         let color = Color::Rgb { r, g, b };
@@ -535,17 +973,33 @@ impl<'a> RenderContext<'a> {
         Some(result)
     }
 
-    // ─── Table Rendering ──────────────────────────────────────
-
-    fn render_table(&self) {
-        if self.table_rows.is_empty() {
+    // ─── Tables ───────────────────────────────────────────────
+
+    fn render_table(
+        &self,
+        alignments: &[Alignment],
+        header: &[Vec<Inline>],
+        rows: &[Vec<Vec<Inline>>],
+        indent: &Indent,
+    ) {
+        let mut table_rows: Vec<Vec<String>> = Vec::new();
+        let has_header = !header.is_empty();
+        if has_header {
+            table_rows.push(header.iter().map(|c| plain_text(c)).collect());
+        }
+        for row in rows {
+            table_rows.push(row.iter().map(|c| plain_text(c)).collect());
+        }
+        if table_rows.is_empty() {
             return;
         }
 
-        let col_count = self.table_rows.iter().map(|r| r.len()).max().unwrap_or(0);
-        let mut widths = vec![3usize; col_count];
+        const MIN_COL_WIDTH: usize = 3;
+
+        let col_count = table_rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        let mut widths = vec![MIN_COL_WIDTH; col_count];
 
-        for row in &self.table_rows {
+        for row in &table_rows {
             for (i, cell) in row.iter().enumerate() {
                 if i < col_count {
                     widths[i] = widths[i].max(cell.width());
@@ -553,55 +1007,86 @@ impl<'a> RenderContext<'a> {
             }
         }
 
+        // Shrink the widest columns toward `MIN_COL_WIDTH` until the table
+        // fits inside the terminal, then word-wrap each cell to its final
+        // width. Overhead is the 2-space indent, the left/right borders and
+        // 2 spaces of padding per column (`│ cell │ cell │`).
+        let overhead = 3 + 3 * col_count;
+        let budget = self.content_width(indent).saturating_sub(overhead);
+        let mut total: usize = widths.iter().sum();
+        while total > budget && total > col_count * MIN_COL_WIDTH {
+            let (idx, _) = widths.iter().enumerate().max_by_key(|&(_, &w)| w).unwrap();
+            if widths[idx] <= MIN_COL_WIDTH {
+                break;
+            }
+            widths[idx] -= 1;
+            total -= 1;
+        }
+
         let border = self.theme.table_border;
 
         // Top border
-        print!("  ");
+        self.print_indent(indent);
+        self.out.raw("  ");
         self.out.colored("┌", border);
         for (i, w) in widths.iter().enumerate() {
             self.out.colored(&"─".repeat(*w + 2), border);
             self.out
                 .colored(if i < col_count - 1 { "┬" } else { "┐" }, border);
         }
-        println!();
+        self.out.newline();
 
         // Rows
-        for (r, row) in self.table_rows.iter().enumerate() {
-            print!("  ");
-            self.out.colored("│", border);
-
-            for (c, w) in widths.iter().enumerate() {
-                let cell = row.get(c).map(|s| s.as_str()).unwrap_or("");
-                let padding = w.saturating_sub(cell.width());
-
-                let (left, right) = match self.table_alignments.get(c) {
-                    Some(Alignment::Center) => (padding / 2, padding - padding / 2),
-                    Some(Alignment::Right) => (padding, 0),
-                    _ => (0, padding),
-                };
+        for (r, row) in table_rows.iter().enumerate() {
+            let wrapped: Vec<Vec<String>> = widths
+                .iter()
+                .enumerate()
+                .map(|(c, w)| {
+                    let cell = row.get(c).map(|s| s.as_str()).unwrap_or("");
+                    wrap_cell(cell, *w)
+                })
+                .collect();
+            let row_height = wrapped.iter().map(|lines| lines.len()).max().unwrap_or(1);
+
+            for line_idx in 0..row_height {
+                self.print_indent(indent);
+                self.out.raw("  ");
+                self.out.colored("│", border);
 
-                print!(" ");
-                for _ in 0..left {
-                    print!(" ");
-                }
+                for (c, w) in widths.iter().enumerate() {
+                    let line = wrapped[c].get(line_idx).map(|s| s.as_str()).unwrap_or("");
+                    let padding = w.saturating_sub(line.width());
 
-                if r == 0 {
-                    self.out.bold_colored(cell, self.theme.table_header);
-                } else {
-                    self.out.colored(cell, self.theme.text);
-                }
+                    let (left, right) = match alignments.get(c) {
+                        Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+                        Some(Alignment::Right) => (padding, 0),
+                        _ => (0, padding),
+                    };
+
+                    self.out.raw(" ");
+                    for _ in 0..left {
+                        self.out.raw(" ");
+                    }
+
+                    if r == 0 && has_header {
+                        self.out.bold_colored(line, self.theme.table_header);
+                    } else {
+                        self.out.colored(line, self.theme.text);
+                    }
 
-                for _ in 0..right {
-                    print!(" ");
+                    for _ in 0..right {
+                        self.out.raw(" ");
+                    }
+                    self.out.raw(" ");
+                    self.out.colored("│", border);
                 }
-                print!(" ");
-                self.out.colored("│", border);
+                self.out.newline();
             }
-            println!();
 
             // Separator after header
-            if r == 0 && self.table_rows.len() > 1 {
-                print!("  ");
+            if r == 0 && has_header && table_rows.len() > 1 {
+                self.print_indent(indent);
+                self.out.raw("  ");
                 self.out.colored("├", border);
                 for (i, w) in widths.iter().enumerate() {
                     self.out.colored(&"─".repeat(*w + 2), border);
@@ -610,73 +1095,160 @@ impl<'a> RenderContext<'a> {
                         border,
                     );
                 }
-                println!();
+                self.out.newline();
             }
         }
 
         // Bottom border
-        print!("  ");
+        self.print_indent(indent);
+        self.out.raw("  ");
         self.out.colored("└", border);
         for (i, w) in widths.iter().enumerate() {
             self.out.colored(&"─".repeat(*w + 2), border);
             self.out
                 .colored(if i < col_count - 1 { "┴" } else { "┘" }, border);
         }
-        println!();
+        self.out.newline();
+    }
+
+    // ─── Table of Contents ─────────────────────────────────────
+
+    /// Slugifies `text` into an anchor id (lowercase, spaces/runs of
+    /// whitespace → a single `-`, everything else non-alphanumeric
+    /// stripped), de-duplicating collisions rustdoc-style by appending
+    /// `-1`, `-2`, ... to repeats of the same slug.
+    fn slugify_anchor(&mut self, text: &str) -> String {
+        let mut slug = String::new();
+        let mut pending_dash = false;
+        for ch in text.to_lowercase().chars() {
+            if ch.is_alphanumeric() {
+                if pending_dash && !slug.is_empty() {
+                    slug.push('-');
+                }
+                pending_dash = false;
+                slug.push(ch);
+            } else if ch.is_whitespace() {
+                pending_dash = true;
+            }
+        }
+        if slug.is_empty() {
+            slug.push_str("section");
+        }
+
+        let count = self.anchor_counts.entry(slug.clone()).or_insert(0);
+        let anchor = if *count == 0 {
+            slug.clone()
+        } else {
+            format!("{}-{}", slug, count)
+        };
+        *count += 1;
+        anchor
+    }
+
+    /// Renders a hierarchical, numbered table of contents from every
+    /// heading seen during the walk, with each entry hyperlinked (via
+    /// `out.hyperlink_start`/`hyperlink_end`) to its slugified anchor.
+    fn render_toc(&self) {
+        if self.toc_headings.is_empty() {
+            return;
+        }
+
+        self.out.newline();
+        self.out.bold_colored("Table of Contents", self.theme.heading1);
+        self.out.newline();
+        self.out.newline();
+
+        let min_level = self.toc_headings.iter().map(|(lvl, _, _)| *lvl).min().unwrap_or(1);
+        let mut counters: Vec<u64> = Vec::new();
+
+        for (lvl, text, anchor) in &self.toc_headings {
+            let depth = (*lvl - min_level) as usize;
+            if counters.len() < depth + 1 {
+                counters.resize(depth + 1, 0);
+            } else {
+                counters.truncate(depth + 1);
+            }
+            counters[depth] += 1;
+
+            let number = counters.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(".");
+
+            self.out.raw(&"  ".repeat(depth));
+            self.out.colored(&format!("{}. ", number), self.theme.list_bullet);
+            self.out.hyperlink_start(&format!("#{}", anchor));
+            self.out.colored(text, self.theme.link);
+            self.out.hyperlink_end();
+            self.out.newline();
+        }
     }
 
     // ─── Helpers ──────────────────────────────────────────────
 
+    /// Cycles headings through a pastel rainbow by level, the same idiom
+    /// `render::json` uses for bracket-depth coloring — nesting becomes
+    /// instantly visible at a glance instead of collapsing levels 5/6 onto
+    /// the same two theme colors as 3/4.
     fn heading_color(&self, level: u8) -> Color {
-        match level {
-            1 => self.theme.heading1,
-            2 => self.theme.heading2,
-            3 => self.theme.heading3,
-            4 => self.theme.heading4,
-            5 => self.theme.heading3,
-            _ => self.theme.heading4,
-        }
+        let (r, g, b) = HEADING_RAINBOW[(level.saturating_sub(1) as usize) % HEADING_RAINBOW.len()];
+        Color::Rgb { r, g, b }
     }
 
-    fn content_width(&self) -> usize {
+    fn base_width(&self) -> usize {
         (self.out.term_width as usize).saturating_sub(4).min(100)
     }
 
-    fn ensure_blank_line(&mut self) {
-        if self.need_newline {
-            println!();
-            self.need_newline = false;
-        }
+    fn content_width(&self, indent: &Indent) -> usize {
+        self.base_width().saturating_sub(indent.width()).max(10)
     }
 
-    fn print_quote_bar(&self) {
-        for _ in 0..self.in_block_quote {
-            print!("  ");
-            self.out.colored("│ ", self.theme.quote_bar);
+    fn ensure_blank_line(&mut self) {
+        if self.need_blank {
+            self.out.newline();
+            self.need_blank = false;
         }
     }
 
-    fn print_list_indent(&self) {
-        let depth = self.list_stack.len().saturating_sub(1);
-        for _ in 0..depth {
-            print!("    ");
+    fn print_indent(&self, indent: &Indent) {
+        for seg in &indent.segs {
+            match seg {
+                IndentSeg::Quote => {
+                    self.out.raw("  ");
+                    self.out.colored("│ ", self.theme.quote_bar);
+                }
+                IndentSeg::Spaces(n) => self.out.raw(&" ".repeat(*n)),
+            }
         }
     }
+}
 
-    fn print_indent(&self) {
-        if self.in_block_quote > 0 {
-            self.print_quote_bar();
-        }
+fn as_rgb(color: Color, fallback: (u8, u8, u8)) -> (u8, u8, u8) {
+    if let Color::Rgb { r, g, b } = color {
+        (r, g, b)
+    } else {
+        fallback
     }
 }
 
-fn heading_level(level: HeadingLevel) -> u8 {
-    match level {
-        HeadingLevel::H1 => 1,
-        HeadingLevel::H2 => 2,
-        HeadingLevel::H3 => 3,
-        HeadingLevel::H4 => 4,
-        HeadingLevel::H5 => 5,
-        HeadingLevel::H6 => 6,
+#[cfg(test)]
+mod table_tests {
+    use super::wrap_cell;
+
+    #[test]
+    fn test_wrap_cell_packs_words() {
+        assert_eq!(wrap_cell("the quick brown fox", 10), vec!["the quick", "brown fox"]);
+    }
+
+    #[test]
+    fn test_wrap_cell_hard_breaks_long_token() {
+        assert_eq!(wrap_cell("supercalifragilistic", 6), vec!["superc", "alifra", "gilist", "ic"]);
+    }
+
+    #[test]
+    fn test_wrap_cell_empty_text() {
+        assert_eq!(wrap_cell("", 10), vec![""]);
+    }
+
+    #[test]
+    fn test_wrap_cell_fits_on_one_line() {
+        assert_eq!(wrap_cell("short", 10), vec!["short"]);
     }
 }
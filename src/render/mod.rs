@@ -0,0 +1,20 @@
+pub mod blame;
+pub mod brief;
+pub mod code;
+pub mod csv;
+pub mod diff;
+pub mod emitter;
+pub mod grep;
+pub mod hex;
+pub mod highlighter;
+pub mod image;
+pub mod json;
+pub mod markdown;
+pub mod org;
+pub mod plain;
+pub mod showall;
+pub mod sidebyside;
+pub mod stats;
+pub mod syntax;
+pub mod toml;
+pub mod yaml;
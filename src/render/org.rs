@@ -0,0 +1,361 @@
+//! Org-mode renderer with colored headlines, keywords, and blocks
+//!
+//! Line-based parser that preserves original formatting, in the same
+//! style as the TOML/YAML renderers. A small amount of state — whether
+//! we're inside a `#+BEGIN_.../#+END_...` block — carries across lines,
+//! since block content is rendered differently from the rest of the
+//! document. Within a line, `render_inline` recognizes timestamps and
+//! Org's inline markup (`*bold*`, `/italic/`, `=verbatim=`, `~code~`,
+//! `[[link][desc]]`) left to right, and `render_checkbox` colors a list
+//! item's leading `[ ]`/`[X]` checkbox, always keeping the original
+//! delimiters in the output rather than stripping them.
+//!
+//! A `#+BEGIN_SRC <lang>` block's language is captured and its body is
+//! highlighted through `render::code::LineHighlighter` — the same
+//! syntect-backed highlighting `render::code::render` gives a standalone
+//! code file, just driven one line at a time (Markdown's fenced code blocks
+//! are instead highlighted a whole block at once by `render::markdown`'s
+//! own syntect pass).
+
+use crate::output::Output;
+use crate::render::code::LineHighlighter;
+use crate::theme::Theme;
+
+/// State carried across lines while inside a `#+BEGIN_.../#+END_...`
+/// block: the block name (for matching the `#+END_` marker and picking a
+/// fallback style) and, for a `#+BEGIN_SRC <lang>` block that names a
+/// language, a live highlighter for its body.
+struct BlockState {
+    name: String,
+    highlighter: Option<LineHighlighter>,
+}
+
+pub fn render(content: &str, theme: &Theme, out: &Output) {
+    let mut block: Option<BlockState> = None;
+    for line in content.lines() {
+        render_line(line, theme, out, &mut block);
+        out.newline();
+    }
+}
+
+fn render_line(line: &str, theme: &Theme, out: &Output, block: &mut Option<BlockState>) {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+
+    if let Some(state) = block {
+        if let Some(end_name) = strip_block_marker(trimmed, "#+END_") {
+            if end_name.eq_ignore_ascii_case(&state.name) {
+                out.raw(indent);
+                out.bold_colored(trimmed, theme.json_key);
+                *block = None;
+                return;
+            }
+        }
+        out.raw(indent);
+        render_block_content(trimmed, state, theme, out);
+        return;
+    }
+
+    if trimmed.is_empty() {
+        return;
+    }
+
+    // Block start: #+BEGIN_name ... (case-insensitive, as Org allows both).
+    if let Some((name, rest)) = strip_block_marker_with_rest(trimmed, "#+BEGIN_") {
+        out.raw(indent);
+        out.bold_colored(trimmed, theme.json_key);
+        let lang = rest.split_whitespace().next().unwrap_or("");
+        let highlighter =
+            if name.eq_ignore_ascii_case("SRC") && !lang.is_empty() { Some(LineHighlighter::new(lang, theme)) } else { None };
+        *block = Some(BlockState { name: name.to_string(), highlighter });
+        return;
+    }
+
+    // Headline: one or more '*' immediately followed by a space.
+    let stars = trimmed.chars().take_while(|&c| c == '*').count();
+    if stars > 0 && trimmed.as_bytes().get(stars) == Some(&b' ') {
+        render_headline(line, stars, theme, out);
+        return;
+    }
+
+    // Document keyword: #+KEY: value
+    if let Some(rest) = trimmed.strip_prefix("#+") {
+        if let Some(colon) = rest.find(':') {
+            out.raw(indent);
+            out.bold_colored(&format!("#+{}", &rest[..colon]), theme.json_key);
+            out.colored(":", theme.json_key);
+            let value = rest[colon + 1..].trim_start();
+            if !value.is_empty() {
+                out.raw(" ");
+                render_inline(value, theme, out);
+            }
+            return;
+        }
+    }
+
+    // Table row: | a | b |
+    if trimmed.starts_with('|') {
+        out.raw(indent);
+        render_table_row(trimmed, theme, out);
+        return;
+    }
+
+    // List item: "- ", "+ ", "N. ", optionally followed by a "[ ]"/"[X]" checkbox
+    if trimmed.starts_with("- ") || trimmed.starts_with("+ ") || is_ordered_item(trimmed) {
+        out.raw(indent);
+        let marker_len = trimmed.find(' ').map(|p| p + 1).unwrap_or(trimmed.len());
+        out.colored(&trimmed[..marker_len], theme.list_bullet);
+        let after_marker = render_checkbox(&trimmed[marker_len..], theme, out);
+        render_inline(after_marker, theme, out);
+        return;
+    }
+
+    render_inline(line, theme, out);
+}
+
+/// Recognizes a task-list checkbox (`[ ]` open, `[X]`/`[x]` done, `[-]`
+/// partially done) at the start of `text`, coloring it and returning
+/// whatever follows it unchanged.
+fn render_checkbox<'a>(text: &'a str, theme: &Theme, out: &Output) -> &'a str {
+    for mark in ["[ ]", "[X]", "[x]", "[-]"] {
+        if let Some(rest) = text.strip_prefix(mark) {
+            let color = if mark == "[ ]" { theme.list_bullet } else { theme.alert_tip };
+            out.bold_colored(mark, color);
+            return rest;
+        }
+    }
+    text
+}
+
+/// Renders a `| a | b |` pipe-table row. A row made up of only
+/// `-`/`+`/`:`/`|` (the `|---+---|` header separator) is dimmed as a
+/// whole; otherwise each `|` is colored like `theme.json_bracket` and
+/// each cell's text goes through `render_inline`.
+fn render_table_row(trimmed: &str, theme: &Theme, out: &Output) {
+    if trimmed.chars().all(|c| matches!(c, '|' | '-' | '+' | ':')) {
+        out.dim(trimmed, theme.table_border);
+        return;
+    }
+
+    let pipes: Vec<usize> = trimmed.match_indices('|').map(|(i, _)| i).collect();
+    for pair in pipes.windows(2) {
+        out.colored("|", theme.json_bracket);
+        render_inline(&trimmed[pair[0] + 1..pair[1]], theme, out);
+    }
+    out.colored("|", theme.json_bracket);
+    if let Some(&last) = pipes.last() {
+        let after = &trimmed[last + 1..];
+        if !after.is_empty() {
+            render_inline(after, theme, out);
+        }
+    }
+}
+
+fn render_headline(line: &str, stars: usize, theme: &Theme, out: &Output) {
+    let indent = &line[..line.len() - line.trim_start().len()];
+    out.raw(indent);
+    let color = heading_color(stars, theme);
+    out.bold_colored(&"*".repeat(stars), color);
+
+    let rest = line.trim_start()[stars..].trim_start();
+    out.raw(" ");
+
+    let (keyword, body) = strip_todo_keyword(rest);
+    if let Some(kw) = keyword {
+        out.bold_colored(kw, theme.alert_tip);
+        out.raw(" ");
+    }
+    out.bold_colored(body, color);
+}
+
+fn strip_todo_keyword(text: &str) -> (Option<&str>, &str) {
+    for kw in ["TODO", "DONE", "NEXT", "WAITING", "CANCELLED"] {
+        if let Some(rest) = text.strip_prefix(kw) {
+            if rest.starts_with(' ') {
+                return (Some(kw), rest.trim_start());
+            }
+        }
+    }
+    (None, text)
+}
+
+fn heading_color(stars: usize, theme: &Theme) -> crossterm::style::Color {
+    match stars {
+        1 => theme.heading1,
+        2 => theme.heading2,
+        3 => theme.heading3,
+        _ => theme.heading4,
+    }
+}
+
+fn is_ordered_item(trimmed: &str) -> bool {
+    let digits: String = trimmed.chars().take_while(|c| c.is_ascii_digit()).collect();
+    if digits.is_empty() {
+        return false;
+    }
+    trimmed[digits.len()..].starts_with(". ") || trimmed[digits.len()..].starts_with(") ")
+}
+
+/// Strips a `#+BEGIN_`/`#+END_` marker (case-insensitively) and returns the
+/// block name that follows, up to the next whitespace — e.g. `"SRC rust"`
+/// yields `"SRC"`.
+fn strip_block_marker<'a>(trimmed: &'a str, marker: &str) -> Option<&'a str> {
+    let rest = trimmed
+        .strip_prefix(marker)
+        .or_else(|| trimmed.strip_prefix(&marker.to_ascii_lowercase()))?;
+    Some(rest.split_whitespace().next().unwrap_or(""))
+}
+
+/// Like `strip_block_marker`, but also returns whatever follows the block
+/// name on the line — for `#+BEGIN_SRC <lang>` that's the language.
+fn strip_block_marker_with_rest<'a>(trimmed: &'a str, marker: &str) -> Option<(&'a str, &'a str)> {
+    let after = trimmed
+        .strip_prefix(marker)
+        .or_else(|| trimmed.strip_prefix(&marker.to_ascii_lowercase()))?
+        .trim_start();
+    let name_len = after.find(char::is_whitespace).unwrap_or(after.len());
+    let (name, rest) = after.split_at(name_len);
+    Some((name, rest.trim_start()))
+}
+
+/// Renders the body of a `#+BEGIN_.../#+END_...` block. A `#+BEGIN_SRC
+/// <lang>` block that names a language delegates to its `LineHighlighter`
+/// (a language syntect has no grammar for is highlighted as plain text);
+/// otherwise source/example blocks get the code color,
+/// quotes get the quote color, and everything else (comments, verses, ...)
+/// is dimmed.
+fn render_block_content(text: &str, state: &mut BlockState, theme: &Theme, out: &Output) {
+    if let Some(highlighter) = state.highlighter.as_mut() {
+        highlighter.render_line(text, out);
+        return;
+    }
+    match state.name.to_ascii_uppercase().as_str() {
+        "SRC" | "EXAMPLE" => out.colored(text, theme.code_fg),
+        "QUOTE" => out.colored(text, theme.quote),
+        _ => out.dim(text, theme.text),
+    }
+}
+
+/// Renders `text` left to right, recognizing Org timestamps, `[[link][desc]]`
+/// links, and `*bold*`/`/italic/`/`=verbatim=`/`~code~` inline markup, and
+/// coloring everything else `theme.text`. Delimiters are always kept in the
+/// output — this renderer preserves formatting rather than reflowing it.
+fn render_inline(text: &str, theme: &Theme, out: &Output) {
+    let mut rest = text;
+    loop {
+        match find_inline_token(rest) {
+            Some((start, end, kind)) => {
+                if start > 0 {
+                    out.colored(&rest[..start], theme.text);
+                }
+                render_inline_token(&rest[start..end], kind, theme, out);
+                rest = &rest[end..];
+            }
+            None => {
+                if !rest.is_empty() {
+                    out.colored(rest, theme.text);
+                }
+                break;
+            }
+        }
+    }
+}
+
+enum InlineKind {
+    Timestamp,
+    Link { url_len: usize, desc_start: usize },
+    Bold,
+    Italic,
+    Verbatim,
+    Code,
+}
+
+fn render_inline_token(span: &str, kind: InlineKind, theme: &Theme, out: &Output) {
+    match kind {
+        InlineKind::Timestamp => out.colored(span, theme.blame_date),
+        InlineKind::Bold => out.bold_colored(span, theme.bold),
+        InlineKind::Italic => out.italic_colored(span, theme.text),
+        InlineKind::Verbatim => out.colored(span, theme.quote),
+        InlineKind::Code => out.colored(span, theme.code_fg),
+        InlineKind::Link { url_len, desc_start } => {
+            out.colored("[[", theme.json_bracket);
+            let url = &span[2..2 + url_len];
+            if desc_start > 0 {
+                out.underline_colored(url, theme.link_url);
+                out.colored("][", theme.json_bracket);
+                out.underline_colored(&span[desc_start..span.len() - 2], theme.link);
+            } else {
+                out.underline_colored(url, theme.link);
+            }
+            out.colored("]]", theme.json_bracket);
+        }
+    }
+}
+
+/// Finds the first recognized inline-markup span in `text` — an Org
+/// timestamp, `[[url]]`/`[[url][desc]]` link, or `*bold*`/`/italic/`/
+/// `=verbatim=`/`~code~` span — returning its byte range and kind.
+fn find_inline_token(text: &str) -> Option<(usize, usize, InlineKind)> {
+    let mut i = 0;
+    while i < text.len() {
+        let ch = text[i..].chars().next().unwrap();
+        if text[i..].starts_with("[[") {
+            if let Some((len, url_len, desc_start)) = parse_org_link(&text[i..]) {
+                return Some((i, i + len, InlineKind::Link { url_len, desc_start }));
+            }
+        } else if (ch == '<' || ch == '[') && is_date_at(&text[i + ch.len_utf8()..]) {
+            let close = if ch == '<' { '>' } else { ']' };
+            if let Some(rel) = text[i..].find(close) {
+                return Some((i, i + rel + close.len_utf8(), InlineKind::Timestamp));
+            }
+        } else if matches!(ch, '*' | '/' | '=' | '~') {
+            if let Some(rel) = find_closing_marker(&text[i + 1..], ch) {
+                let kind = match ch {
+                    '*' => InlineKind::Bold,
+                    '/' => InlineKind::Italic,
+                    '=' => InlineKind::Verbatim,
+                    _ => InlineKind::Code,
+                };
+                return Some((i, i + 1 + rel + 1, kind));
+            }
+        }
+        i += ch.len_utf8();
+    }
+    None
+}
+
+/// Finds the closing `marker` for an inline span, requiring non-empty,
+/// single-line content so an unrelated later marker on the same line
+/// doesn't get treated as closing an empty pair.
+fn find_closing_marker(text: &str, marker: char) -> Option<usize> {
+    let rel = text.find(marker)?;
+    if rel == 0 || text[..rel].contains('\n') {
+        return None;
+    }
+    Some(rel)
+}
+
+/// Parses a `[[url]]`/`[[url][desc]]` link starting at `text[0..]` (which
+/// must begin with `[[`). Returns the total byte length of the `[[...]]`
+/// span, the byte length of the url, and the offset of `desc` within the
+/// span (0 when there's no separate description).
+fn parse_org_link(text: &str) -> Option<(usize, usize, usize)> {
+    let end = text.find("]]")?;
+    let inner = &text[2..end];
+    let total = end + 2;
+    if let Some(sep) = inner.find("][") {
+        Some((total, sep, 2 + sep + 2))
+    } else {
+        Some((total, inner.len(), 0))
+    }
+}
+
+fn is_date_at(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    bytes.len() >= 10
+        && bytes[0..4].iter().all(u8::is_ascii_digit)
+        && bytes[4] == b'-'
+        && bytes[5..7].iter().all(u8::is_ascii_digit)
+        && bytes[7] == b'-'
+        && bytes[8..10].iter().all(u8::is_ascii_digit)
+}
@@ -1,11 +1,11 @@
 use crate::output::Output;
 use crate::theme::Theme;
 
-pub fn render(content: &str, line_numbers: bool, theme: &Theme, _out: &Output) {
+pub fn render(content: &str, line_numbers: bool, theme: &Theme, out: &Output) {
     if !line_numbers {
-        print!("{}", content);
+        out.raw(content);
         if !content.ends_with('\n') {
-            println!();
+            out.newline();
         }
         return;
     }
@@ -14,16 +14,14 @@ pub fn render(content: &str, line_numbers: bool, theme: &Theme, _out: &Output) {
     let width = format!("{}", lines.len()).len();
 
     for (i, line) in lines.iter().enumerate() {
-        print!(
-            "\x1b[38;2;{};{};{}m {:>w$} │ \x1b[0m{}",
-            color_r(theme.line_number),
-            color_g(theme.line_number),
-            color_b(theme.line_number),
+        out.raw(&format!(
+            "{} {:>w$} │ \x1b[0m{}",
+            out.ansi_fg(color_r(theme.line_number), color_g(theme.line_number), color_b(theme.line_number)),
             i + 1,
             line,
             w = width
-        );
-        println!();
+        ));
+        out.newline();
     }
 }
 
@@ -1,6 +1,12 @@
 //! Show-all renderer: visualizes invisible characters with symbolic replacements.
 //! Tab → ⇥, space → ·, CR → ←, LF → ↵, control chars → ^X, NBSP → ⍽,
 //! zero-width chars → [U+XXXX]. Always displays line numbers.
+//!
+//! Also flags Unicode bidirectional override/isolate controls (the
+//! "Trojan Source" family: U+202A-U+202E, U+2066-U+2069) with a named
+//! badge like `[RLO]`, since they can make source display differently from
+//! how it executes. `count_suspicious` exposes the same detection for the
+//! top-level `--audit` flag, so a file can be scanned without rendering it.
 
 use crate::output::Output;
 use crate::theme::Theme;
@@ -15,21 +21,52 @@ pub fn render(content: &str, theme: &Theme, out: &Output) {
         render_line(line, theme, out);
 
         out.dim("↵", theme.line_number);
-        println!();
+        out.newline();
+    }
+
+    let suspicious = count_suspicious(content);
+    if suspicious > 0 {
+        out.newline();
+        out.colored(
+            &format!(
+                "⚠ {} suspicious bidi/zero-width character(s) found",
+                suspicious
+            ),
+            theme.alert_caution,
+        );
+        out.newline();
+    }
+}
+
+/// Counts zero-width and bidirectional-control characters in `content` —
+/// the same set `render_line` badges, usable without rendering.
+pub fn count_suspicious(content: &str) -> usize {
+    content.chars().filter(|&ch| is_zero_width(ch) || bidi_label(ch).is_some()).count()
+}
+
+fn is_zero_width(ch: char) -> bool {
+    matches!(ch, '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{FEFF}')
+}
+
+/// Maps a Unicode bidi override/isolate control to its short badge label.
+fn bidi_label(ch: char) -> Option<&'static str> {
+    match ch {
+        '\u{202A}' => Some("LRE"),
+        '\u{202B}' => Some("RLE"),
+        '\u{202C}' => Some("PDF"),
+        '\u{202D}' => Some("LRO"),
+        '\u{202E}' => Some("RLO"),
+        '\u{2066}' => Some("LRI"),
+        '\u{2067}' => Some("RLI"),
+        '\u{2068}' => Some("FSI"),
+        '\u{2069}' => Some("PDI"),
+        _ => None,
     }
 }
 
 fn print_line_number(num: usize, width: usize, theme: &Theme, out: &Output) {
     let (r, g, b) = rgb(theme.line_number);
-    print!(
-        "\x1b[38;2;{};{};{}m {:>w$} │ \x1b[0m",
-        r,
-        g,
-        b,
-        num,
-        w = width
-    );
-    let _ = out;
+    out.raw(&format!("{} {:>w$} │ \x1b[0m", out.ansi_fg(r, g, b), num, w = width));
 }
 
 fn render_line(line: &str, theme: &Theme, out: &Output) {
@@ -71,8 +108,13 @@ fn render_line(line: &str, theme: &Theme, out: &Output) {
                 out.colored(&format!("[U+{:04X}]", ch as u32), theme.alert_caution);
                 col += 8;
             }
+            _ if bidi_label(ch).is_some() => {
+                let label = bidi_label(ch).unwrap();
+                out.colored(&format!("[{}]", label), theme.alert_caution);
+                col += label.len() + 2;
+            }
             _ => {
-                print!("{}", ch);
+                out.raw(&ch.to_string());
                 col += 1;
             }
         }
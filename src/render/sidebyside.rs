@@ -0,0 +1,160 @@
+//! Side-by-side two-file comparison — syntax-highlighted columns aligned by
+//! line number, in the spirit of `diff -y`.
+//!
+//! Each file is highlighted independently via the same syntect setup
+//! `render::code` uses, then wrapped to half the terminal width so long
+//! lines spill onto extra aligned rows instead of overflowing the column.
+
+use crossterm::style::Color;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{FontStyle, Style};
+use syntect::util::LinesWithEndings;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::output::Output;
+use crate::render::syntax::{find_syntax, theme_set};
+use crate::theme::Theme;
+
+#[derive(Clone)]
+struct Span {
+    text: String,
+    color: Color,
+    bold: bool,
+    italic: bool,
+}
+
+pub fn render(left_content: &str, right_content: &str, lang: &str, theme: &Theme, out: &Output) {
+    let gutter = " │ ";
+    let col_width = ((out.term_width as usize).saturating_sub(gutter.len()) / 2).max(20);
+
+    let left_rows = highlighted_rows(left_content, lang, theme, col_width);
+    let right_rows = highlighted_rows(right_content, lang, theme, col_width);
+    let row_count = left_rows.len().max(right_rows.len());
+
+    for i in 0..row_count {
+        let empty = Vec::new();
+        let left = left_rows.get(i).unwrap_or(&empty);
+        let right = right_rows.get(i).unwrap_or(&empty);
+
+        let left_width = print_row(left, out);
+        out.raw(&" ".repeat(col_width.saturating_sub(left_width)));
+        out.dim(gutter, theme.line_number);
+        print_row(right, out);
+        out.newline();
+    }
+}
+
+fn print_row(spans: &[Span], out: &Output) -> usize {
+    let mut width = 0;
+    for span in spans {
+        width += UnicodeWidthStr::width(span.text.as_str());
+        if span.bold {
+            out.bold_colored(&span.text, span.color);
+        } else if span.italic {
+            out.italic_colored(&span.text, span.color);
+        } else {
+            out.colored(&span.text, span.color);
+        }
+    }
+    width
+}
+
+/// Highlights `content` line by line, then wraps each line's spans so no row
+/// exceeds `width` display columns.
+fn highlighted_rows(content: &str, lang: &str, theme: &Theme, width: usize) -> Vec<Vec<Span>> {
+    let ts = theme_set();
+    let (ss, syntax) = find_syntax(lang);
+
+    let highlight_theme = ts
+        .themes
+        .get(theme.syntect_theme)
+        .or_else(|| ts.themes.get("Monokai Extended"))
+        .unwrap_or_else(|| ts.themes.values().next().unwrap());
+
+    let mut h = HighlightLines::new(syntax, highlight_theme);
+    let mut rows = Vec::new();
+
+    for line in LinesWithEndings::from(content) {
+        let spans = match h.highlight_line(line, ss) {
+            Ok(ranges) => ranges
+                .into_iter()
+                .map(|(style, text)| Span {
+                    text: text.trim_end_matches(['\n', '\r']).to_string(),
+                    color: syntect_to_crossterm(style),
+                    bold: style.font_style.contains(FontStyle::BOLD),
+                    italic: style.font_style.contains(FontStyle::ITALIC),
+                })
+                .collect(),
+            Err(_) => vec![Span {
+                text: line.trim_end_matches(['\n', '\r']).to_string(),
+                color: theme.text,
+                bold: false,
+                italic: false,
+            }],
+        };
+        rows.extend(wrap_spans(spans, width));
+    }
+
+    if rows.is_empty() {
+        rows.push(Vec::new());
+    }
+    rows
+}
+
+fn wrap_spans(spans: Vec<Span>, width: usize) -> Vec<Vec<Span>> {
+    let mut rows = Vec::new();
+    let mut current: Vec<Span> = Vec::new();
+    let mut current_width = 0usize;
+
+    for span in spans {
+        let mut remaining = span.text.as_str();
+        while !remaining.is_empty() {
+            let avail = width.saturating_sub(current_width);
+            if avail == 0 {
+                rows.push(std::mem::take(&mut current));
+                current_width = 0;
+                continue;
+            }
+
+            let mut taken_bytes = 0usize;
+            let mut taken_width = 0usize;
+            for c in remaining.chars() {
+                let cw = UnicodeWidthChar::width(c).unwrap_or(1);
+                if taken_width + cw > avail {
+                    break;
+                }
+                taken_width += cw;
+                taken_bytes += c.len_utf8();
+            }
+
+            if taken_bytes == 0 {
+                // A single char wider than the whole column: force it onto
+                // its own row rather than looping forever.
+                rows.push(std::mem::take(&mut current));
+                current_width = 0;
+                continue;
+            }
+
+            let (chunk, rest) = remaining.split_at(taken_bytes);
+            current.push(Span {
+                text: chunk.to_string(),
+                color: span.color,
+                bold: span.bold,
+                italic: span.italic,
+            });
+            current_width += taken_width;
+            remaining = rest;
+        }
+    }
+
+    rows.push(current);
+    rows
+}
+
+fn syntect_to_crossterm(style: Style) -> Color {
+    Color::Rgb {
+        r: style.foreground.r,
+        g: style.foreground.g,
+        b: style.foreground.b,
+    }
+}
@@ -0,0 +1,220 @@
+//! `--stats` mode: aggregates `crate::stats::count` per language across all
+//! inputs and prints a tokei-style summary table (or, with `--format=json`,
+//! the same totals as JSON).
+
+use std::collections::HashMap;
+
+use crate::detect::FileFormat;
+use crate::output::Output;
+use crate::stats;
+use crate::theme::Theme;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct LanguageTotals {
+    files: usize,
+    code: usize,
+    comments: usize,
+    blanks: usize,
+}
+
+impl LanguageTotals {
+    fn total(&self) -> usize {
+        self.code + self.comments + self.blanks
+    }
+}
+
+/// Accumulates per-language totals across however many files `--stats` is
+/// pointed at, then renders them once at the end.
+#[derive(Default)]
+pub struct Collector {
+    totals: HashMap<String, LanguageTotals>,
+}
+
+impl Collector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Classifies and counts `content` under `format`'s language label.
+    pub fn add(&mut self, format: &FileFormat, content: &str) {
+        let lang = language_label(format);
+        let counts = stats::count(content, &lang);
+        let entry = self.totals.entry(lang).or_default();
+        entry.files += 1;
+        entry.code += counts.code;
+        entry.comments += counts.comments;
+        entry.blanks += counts.blanks;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.totals.is_empty()
+    }
+
+    /// Prints a bordered table sorted by code lines descending, with a
+    /// bolded totals row.
+    pub fn render(&self, theme: &Theme, out: &Output) {
+        let mut rows: Vec<(&str, &LanguageTotals)> =
+            self.totals.iter().map(|(lang, t)| (lang.as_str(), t)).collect();
+        rows.sort_by(|a, b| b.1.code.cmp(&a.1.code).then_with(|| a.0.cmp(b.0)));
+
+        let lang_width = rows
+            .iter()
+            .map(|(lang, _)| lang.len())
+            .chain(std::iter::once("Language".len()))
+            .max()
+            .unwrap_or(8);
+
+        let totals = rows.iter().fold(LanguageTotals::default(), |mut acc, (_, t)| {
+            acc.files += t.files;
+            acc.code += t.code;
+            acc.comments += t.comments;
+            acc.blanks += t.blanks;
+            acc
+        });
+        let num_width = |get: fn(&LanguageTotals) -> usize| {
+            rows.iter()
+                .map(|(_, t)| get(t))
+                .chain(std::iter::once(get(&totals)))
+                .map(|n| n.to_string().len())
+                .max()
+                .unwrap_or(1)
+        };
+        let widths = [
+            lang_width,
+            num_width(|t| t.files).max("Files".len()),
+            num_width(|t| t.code).max("Code".len()),
+            num_width(|t| t.comments).max("Comments".len()),
+            num_width(|t| t.blanks).max("Blanks".len()),
+            num_width(|t| t.total()).max("Total".len()),
+        ];
+
+        let border_color = theme.table_border;
+        print_border(&widths, '┌', '┬', '┐', border_color, out);
+        print_row(
+            &["Language", "Files", "Code", "Comments", "Blanks", "Total"],
+            &widths,
+            border_color,
+            theme.table_header,
+            true,
+            out,
+        );
+        print_border(&widths, '├', '┼', '┤', border_color, out);
+
+        for (lang, t) in &rows {
+            print_row(
+                &[
+                    lang,
+                    &t.files.to_string(),
+                    &t.code.to_string(),
+                    &t.comments.to_string(),
+                    &t.blanks.to_string(),
+                    &t.total().to_string(),
+                ],
+                &widths,
+                border_color,
+                theme.text,
+                false,
+                out,
+            );
+        }
+
+        print_border(&widths, '├', '┼', '┤', border_color, out);
+        print_row(
+            &[
+                "Total",
+                &totals.files.to_string(),
+                &totals.code.to_string(),
+                &totals.comments.to_string(),
+                &totals.blanks.to_string(),
+                &totals.total().to_string(),
+            ],
+            &widths,
+            border_color,
+            theme.table_header,
+            true,
+            out,
+        );
+        print_border(&widths, '└', '┴', '┘', border_color, out);
+    }
+
+    /// Emits the same totals as a JSON array, sorted by code lines
+    /// descending, for scripting.
+    pub fn render_json(&self, out: &Output) {
+        let mut rows: Vec<(&str, &LanguageTotals)> =
+            self.totals.iter().map(|(lang, t)| (lang.as_str(), t)).collect();
+        rows.sort_by(|a, b| b.1.code.cmp(&a.1.code).then_with(|| a.0.cmp(b.0)));
+
+        let json: Vec<serde_json::Value> = rows
+            .iter()
+            .map(|(lang, t)| {
+                serde_json::json!({
+                    "language": lang,
+                    "files": t.files,
+                    "code": t.code,
+                    "comments": t.comments,
+                    "blanks": t.blanks,
+                    "total": t.total(),
+                })
+            })
+            .collect();
+
+        if let Ok(text) = serde_json::to_string_pretty(&json) {
+            out.raw(&text);
+            out.newline();
+        }
+    }
+}
+
+fn language_label(format: &FileFormat) -> String {
+    match format {
+        FileFormat::Markdown => "Markdown".to_string(),
+        FileFormat::Json => "JSON".to_string(),
+        FileFormat::Csv => "CSV".to_string(),
+        FileFormat::Org => "Org".to_string(),
+        FileFormat::Code(lang) => lang.clone(),
+        FileFormat::Image => "Image".to_string(),
+        FileFormat::Plain => "Plain Text".to_string(),
+    }
+}
+
+fn print_border(
+    widths: &[usize],
+    left: char,
+    mid: char,
+    right: char,
+    color: crossterm::style::Color,
+    out: &Output,
+) {
+    out.raw("  ");
+    out.colored(&left.to_string(), color);
+    for (i, w) in widths.iter().enumerate() {
+        out.colored(&"─".repeat(*w + 2), color);
+        out.colored(&(if i < widths.len() - 1 { mid } else { right }).to_string(), color);
+    }
+    out.newline();
+}
+
+fn print_row(
+    cells: &[&str],
+    widths: &[usize],
+    border_color: crossterm::style::Color,
+    cell_color: crossterm::style::Color,
+    bold: bool,
+    out: &Output,
+) {
+    out.raw("  ");
+    out.colored("│", border_color);
+    for (cell, w) in cells.iter().zip(widths) {
+        let padding = w.saturating_sub(cell.len());
+        out.raw(" ");
+        if bold {
+            out.bold_colored(cell, cell_color);
+        } else {
+            out.colored(cell, cell_color);
+        }
+        out.raw(&" ".repeat(padding));
+        out.raw(" ");
+        out.colored("│", border_color);
+    }
+    out.newline();
+}
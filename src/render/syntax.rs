@@ -0,0 +1,98 @@
+//! Process-wide cache for syntect's `SyntaxSet`/`ThemeSet`, which deserialize
+//! a sizeable binary dump on `load_defaults_*`. Every renderer that
+//! highlights code (`code`, `diff`, `sidebyside`, `blame`, `markdown`) should
+//! call [`syntax_set`]/`theme_set` instead of loading its own copy, so the
+//! cost is paid once per process no matter how many code blocks or files are
+//! rendered.
+//!
+//! [`find_syntax`] additionally folds in a user-supplied extended grammar
+//! set from `vita/syntaxes` (see [`crate::config::user_syntax_dir`]), and
+//! `theme_set` does the same for `vita/themes`, so a user can drop in a
+//! `.sublime-syntax`/`.tmTheme` for a language or color scheme this build
+//! doesn't bundle — same spirit as `config::load` falling back silently on
+//! a missing or malformed file.
+
+use std::sync::OnceLock;
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::{SyntaxReference, SyntaxSet, SyntaxSetBuilder};
+
+use crate::config;
+
+/// Returns the shared default syntax set, loading it on first use.
+pub fn syntax_set() -> &'static SyntaxSet {
+    static CELL: OnceLock<SyntaxSet> = OnceLock::new();
+    CELL.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// Returns the shared default theme set, loading it on first use. Any
+/// `.tmTheme` files under `vita/themes` are folded in alongside the
+/// bundled themes.
+pub fn theme_set() -> &'static ThemeSet {
+    static CELL: OnceLock<ThemeSet> = OnceLock::new();
+    CELL.get_or_init(|| {
+        let mut themes = ThemeSet::load_defaults();
+        if let Some(dir) = config::user_theme_dir() {
+            // A missing directory or a `.tmTheme` that fails to parse just
+            // means nothing extra to add.
+            let _ = themes.add_from_folder(dir);
+        }
+        themes
+    })
+}
+
+/// The extra syntax set loaded from `vita/syntaxes`, if the directory
+/// exists and contains at least one loadable `.sublime-syntax` file.
+fn extra_syntax_set() -> Option<&'static SyntaxSet> {
+    static CELL: OnceLock<Option<SyntaxSet>> = OnceLock::new();
+    CELL.get_or_init(|| {
+        let dir = config::user_syntax_dir()?;
+        if !dir.is_dir() {
+            return None;
+        }
+        let mut builder = SyntaxSetBuilder::new();
+        builder.add_from_folder(&dir, true).ok()?;
+        Some(builder.build())
+    })
+    .as_ref()
+}
+
+/// Theme names available via `theme.syntect_theme` — built-in plus any
+/// user-supplied `.tmTheme` files — sorted for display.
+pub fn available_theme_names() -> Vec<&'static str> {
+    let mut names: Vec<&str> = theme_set().themes.keys().map(String::as_str).collect();
+    names.sort_unstable();
+    names
+}
+
+/// Looks up a syntax by name, extension, then scope token, falling back to
+/// [`crate::detect::syntax_fallback`] for languages this build has no
+/// direct grammar for (e.g. TypeScript → JavaScript). Checks the
+/// user-supplied [`extra_syntax_set`] first, so a custom grammar for a
+/// language already covered by the bundled defaults takes priority.
+///
+/// Returns the matched syntax alongside the `SyntaxSet` that owns it —
+/// callers must pass that same set to `HighlightLines::new`/`highlight_line`,
+/// since a `SyntaxReference` is only valid against the set it came from.
+pub fn find_syntax(lang: &str) -> (&'static SyntaxSet, &'static SyntaxReference) {
+    let lower = lang.to_lowercase();
+
+    if let Some(extra) = extra_syntax_set() {
+        if let Some(syntax) = lookup(extra, lang, &lower) {
+            return (extra, syntax);
+        }
+    }
+
+    let defaults = syntax_set();
+    let syntax = lookup(defaults, lang, &lower).unwrap_or_else(|| defaults.find_syntax_plain_text());
+    (defaults, syntax)
+}
+
+fn lookup<'a>(set: &'a SyntaxSet, lang: &str, lower: &str) -> Option<&'a SyntaxReference> {
+    set.find_syntax_by_name(lang)
+        .or_else(|| set.find_syntax_by_extension(lower))
+        .or_else(|| set.find_syntax_by_token(lower))
+        .or_else(|| {
+            let fb = crate::detect::syntax_fallback(lang);
+            set.find_syntax_by_name(&fb).or_else(|| set.find_syntax_by_token(&fb))
+        })
+}
@@ -10,7 +10,7 @@ use crate::theme::Theme;
 pub fn render(content: &str, theme: &Theme, out: &Output) {
     for line in content.lines() {
         render_line(line, theme, out);
-        println!();
+        out.newline();
     }
 }
 
@@ -24,7 +24,7 @@ fn render_line(line: &str, theme: &Theme, out: &Output) {
     // Comment
     if trimmed.starts_with('#') {
         let indent = &line[..line.len() - trimmed.len()];
-        print!("{}", indent);
+        out.raw(indent);
         out.dim(trimmed, theme.line_number);
         return;
     }
@@ -32,7 +32,7 @@ fn render_line(line: &str, theme: &Theme, out: &Output) {
     // Section header: [[array]] or [table]
     if trimmed.starts_with('[') {
         let indent = &line[..line.len() - trimmed.len()];
-        print!("{}", indent);
+        out.raw(indent);
         render_section_header(trimmed, theme, out);
         return;
     }
@@ -40,14 +40,14 @@ fn render_line(line: &str, theme: &Theme, out: &Output) {
     // Key = value
     if let Some(eq_pos) = find_equals(trimmed) {
         let indent = &line[..line.len() - trimmed.len()];
-        print!("{}", indent);
+        out.raw(indent);
         let key = &trimmed[..eq_pos];
         let rest = &trimmed[eq_pos..];
         out.colored(key.trim_end(), theme.json_key);
         // Print spacing between key and '='
         let key_trimmed_len = key.trim_end().len();
         if key_trimmed_len < key.len() {
-            print!("{}", &key[key_trimmed_len..]);
+            out.raw(&key[key_trimmed_len..]);
         }
         out.colored("=", theme.json_bracket);
         if rest.len() > 1 {
@@ -55,7 +55,7 @@ fn render_line(line: &str, theme: &Theme, out: &Output) {
             // Preserve leading space after '='
             let value_trimmed = value_part.trim_start();
             let spaces = &value_part[..value_part.len() - value_trimmed.len()];
-            print!("{}", spaces);
+            out.raw(spaces);
             render_value(value_trimmed, theme, out);
         }
         return;
@@ -76,7 +76,7 @@ fn render_section_header(trimmed: &str, theme: &Theme, out: &Output) {
             // Trailing comment
             let after = trimmed[end + 2..].trim();
             if !after.is_empty() {
-                print!(" ");
+                out.raw(" ");
                 out.dim(after, theme.line_number);
             }
         } else {
@@ -90,7 +90,7 @@ fn render_section_header(trimmed: &str, theme: &Theme, out: &Output) {
             out.bold_colored("]", theme.json_bracket);
             let after = trimmed[2 + end..].trim();
             if !after.is_empty() {
-                print!(" ");
+                out.raw(" ");
                 out.dim(after, theme.line_number);
             }
         } else {
@@ -131,7 +131,7 @@ fn render_value(value: &str, theme: &Theme, out: &Output) {
     render_value_core(val, theme, out);
 
     if let Some(c) = comment {
-        print!(" ");
+        out.raw(" ");
         out.dim(c, theme.line_number);
     }
 }
@@ -157,15 +157,18 @@ fn render_value_core(val: &str, theme: &Theme, out: &Output) {
         return;
     }
 
-    // Number (integer, float, hex, oct, bin, inf, nan, with optional sign/underscores)
-    if is_toml_number(val) {
-        out.colored(val, theme.json_number);
+    // Date/time values — date, separator, time, and offset each colored
+    // separately rather than as one opaque string. Checked before
+    // `is_toml_number` since a bare local date like `1979-05-27` is
+    // digits-and-dashes and would otherwise match the number check first.
+    if let Some(span) = parse_toml_datetime(val) {
+        render_toml_datetime(val, &span, theme, out);
         return;
     }
 
-    // Date/time values — treat as strings
-    if is_toml_datetime(val) {
-        out.colored(val, theme.json_string);
+    // Number (integer, float, hex, oct, bin, inf, nan, with optional sign/underscores)
+    if is_toml_number(val) {
+        out.colored(val, theme.json_number);
         return;
     }
 
@@ -195,13 +198,13 @@ fn render_inline_array(val: &str, theme: &Theme, out: &Output) {
         }
         let trimmed = part.trim();
         if trimmed.is_empty() {
-            print!("{}", part);
+            out.raw(part);
         } else {
             let leading = &part[..part.len() - part.trim_start().len()];
             let trailing = &part[part.trim_end().len()..];
-            print!("{}", leading);
+            out.raw(leading);
             render_value_core(trimmed, theme, out);
-            print!("{}", trailing);
+            out.raw(trailing);
         }
     }
     out.colored("]", theme.json_bracket);
@@ -218,16 +221,16 @@ fn render_inline_table(val: &str, theme: &Theme, out: &Output) {
         let trimmed = part.trim();
         if let Some(eq) = trimmed.find('=') {
             let leading = &part[..part.len() - part.trim_start().len()];
-            print!("{}", leading);
+            out.raw(leading);
             let key = trimmed[..eq].trim_end();
             let v = trimmed[eq + 1..].trim();
             out.colored(key, theme.json_key);
-            print!(" ");
+            out.raw(" ");
             out.colored("=", theme.json_bracket);
-            print!(" ");
+            out.raw(" ");
             render_value_core(v, theme, out);
         } else {
-            print!("{}", part);
+            out.raw(part);
         }
     }
     out.colored("}", theme.json_bracket);
@@ -325,9 +328,139 @@ fn is_toml_number(val: &str) -> bool {
         && s.chars().next().map_or(false, |c| c.is_ascii_digit())
 }
 
-fn is_toml_datetime(val: &str) -> bool {
-    // Simple heuristic: contains digit-digit-digit-digit and either '-' or ':'
-    val.len() >= 10
-        && val.as_bytes()[4] == b'-'
-        && val.chars().take(4).all(|c| c.is_ascii_digit())
+/// A recognized TOML datetime value (offset date-time, local date-time,
+/// local date, or local time — see the TOML spec), split into the byte
+/// ranges of each present component so the caller can color them
+/// independently instead of treating the whole value as one string.
+struct DateTimeSpan {
+    date: Option<std::ops::Range<usize>>,
+    sep: Option<std::ops::Range<usize>>,
+    time: Option<std::ops::Range<usize>>,
+    offset: Option<std::ops::Range<usize>>,
+}
+
+/// Parses `val` as one of the four TOML datetime shapes, validating field
+/// ranges (month 1-12, day 1-31, hour 0-23, minute/second 0-59, with a leap
+/// second allowance up to 60) so a bare value like `"1-2-3-4"` isn't
+/// misrecognized just because it has dashes and digits.
+fn parse_toml_datetime(val: &str) -> Option<DateTimeSpan> {
+    let bytes = val.as_bytes();
+    let mut pos = 0;
+
+    let date = if is_date_component(bytes, pos) {
+        let r = pos..pos + 10;
+        pos += 10;
+        Some(r)
+    } else {
+        None
+    };
+
+    let sep = if date.is_some() && matches!(bytes.get(pos), Some(b'T' | b't' | b' ')) {
+        let r = pos..pos + 1;
+        pos += 1;
+        Some(r)
+    } else {
+        None
+    };
+
+    let time = match time_component_len(&bytes[pos..]) {
+        Some(len) => {
+            let r = pos..pos + len;
+            pos += len;
+            Some(r)
+        }
+        None if sep.is_some() => return None, // separator with no time after it
+        None => None,
+    };
+
+    let offset = time.as_ref().and_then(|_| {
+        if matches!(bytes.get(pos), Some(b'Z' | b'z')) {
+            let r = pos..pos + 1;
+            pos += 1;
+            Some(r)
+        } else if is_offset_component(&bytes[pos..]) {
+            let r = pos..pos + 6;
+            pos += 6;
+            Some(r)
+        } else {
+            None
+        }
+    });
+
+    if pos != bytes.len() || (date.is_none() && time.is_none()) {
+        return None;
+    }
+    Some(DateTimeSpan { date, sep, time, offset })
+}
+
+fn render_toml_datetime(val: &str, span: &DateTimeSpan, theme: &Theme, out: &Output) {
+    if let Some(r) = &span.date {
+        out.colored(&val[r.clone()], theme.json_string);
+    }
+    if let Some(r) = &span.sep {
+        out.colored(&val[r.clone()], theme.json_bracket);
+    }
+    if let Some(r) = &span.time {
+        out.colored(&val[r.clone()], theme.json_number);
+    }
+    if let Some(r) = &span.offset {
+        out.dim(&val[r.clone()], theme.line_number);
+    }
+}
+
+fn is_date_component(bytes: &[u8], pos: usize) -> bool {
+    let Some(b) = bytes.get(pos..pos + 10) else { return false };
+    digits_n(&b[0..4]) && b[4] == b'-' && digits_n(&b[5..7]) && b[7] == b'-' && digits_n(&b[8..10])
+        && in_range(&b[5..7], 1, 12)
+        && in_range(&b[8..10], 1, 31)
+}
+
+/// Parses `HH:MM:SS` plus an optional `.` fractional-seconds suffix,
+/// returning the number of bytes consumed.
+fn time_component_len(bytes: &[u8]) -> Option<usize> {
+    let b = bytes.get(0..8)?;
+    let valid = digits_n(&b[0..2])
+        && b[2] == b':'
+        && digits_n(&b[3..5])
+        && b[5] == b':'
+        && digits_n(&b[6..8])
+        && in_range(&b[0..2], 0, 23)
+        && in_range(&b[3..5], 0, 59)
+        && in_range(&b[6..8], 0, 60); // allow a leap second
+    if !valid {
+        return None;
+    }
+
+    let mut len = 8;
+    if bytes.get(8) == Some(&b'.') {
+        let mut i = 9;
+        while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+            i += 1;
+        }
+        if i > 9 {
+            len = i;
+        }
+    }
+    Some(len)
+}
+
+fn is_offset_component(bytes: &[u8]) -> bool {
+    let Some(b) = bytes.get(0..6) else { return false };
+    matches!(b[0], b'+' | b'-')
+        && digits_n(&b[1..3])
+        && b[3] == b':'
+        && digits_n(&b[4..6])
+        && in_range(&b[1..3], 0, 23)
+        && in_range(&b[4..6], 0, 59)
+}
+
+fn digits_n(b: &[u8]) -> bool {
+    !b.is_empty() && b.iter().all(u8::is_ascii_digit)
+}
+
+fn in_range(b: &[u8], lo: u32, hi: u32) -> bool {
+    std::str::from_utf8(b)
+        .ok()
+        .and_then(|s| s.parse::<u32>().ok())
+        .is_some_and(|n| (lo..=hi).contains(&n))
 }
@@ -3,239 +3,230 @@
 //! Line-based parser that preserves original formatting.
 //! Keys get key color, values are colored by detected type
 //! (bool, null, number, string).
+//!
+//! `render`/`render_line` are thin wrappers over `render_with_emitter`
+//! feeding an `AnsiEmitter` — the line-classification logic itself lives
+//! once, here, and both the ANSI terminal path and the structured
+//! `JsonEmitter` path (one record per key/value/comment token) drive it.
 
 use crate::output::Output;
+use crate::render::emitter::{AnsiEmitter, Emitter, Span, TokenKind};
 use crate::theme::Theme;
 
 pub fn render(content: &str, theme: &Theme, out: &Output) {
-    for line in content.lines() {
-        render_line(line, theme, out);
-        println!();
+    render_with_emitter(content, &mut AnsiEmitter::new(theme, out));
+}
+
+/// Renders a single line through an `AnsiEmitter`. Kept for callers (e.g.
+/// `Highlighter` impls) that drive one line at a time rather than a whole
+/// document.
+pub fn render_line(line: &str, theme: &Theme, out: &Output) {
+    let mut emitter = AnsiEmitter::new(theme, out);
+    render_line_with_emitter(line, 0, &mut emitter);
+}
+
+/// Same line classification as `render`, but writes through `emitter` —
+/// pass a `JsonEmitter` to get one record per key/value/comment token
+/// instead of an ANSI dump.
+pub fn render_with_emitter(content: &str, emitter: &mut dyn Emitter) {
+    for (line_idx, line) in content.lines().enumerate() {
+        render_line_with_emitter(line, line_idx, emitter);
+        emitter.end_line();
     }
 }
 
-fn render_line(line: &str, theme: &Theme, out: &Output) {
+fn render_line_with_emitter(line: &str, line_idx: usize, emitter: &mut dyn Emitter) {
     let trimmed = line.trim();
-
     if trimmed.is_empty() {
         return;
     }
 
-    // Comment
+    let indent_len = line.len() - line.trim_start().len();
+
     if trimmed.starts_with('#') {
-        let indent = &line[..line.len() - trimmed.len()];
-        print!("{}", indent);
-        out.dim(trimmed, theme.line_number);
+        let start = line.len() - trimmed.len();
+        emitter.literal(&line[..start]);
+        emit_span(emitter, TokenKind::Comment, line_idx, start, trimmed);
         return;
     }
 
-    // Document separator
     if trimmed == "---" || trimmed == "..." {
-        let indent = &line[..line.len() - trimmed.len()];
-        print!("{}", indent);
-        out.colored(trimmed, theme.hr);
+        let start = line.len() - trimmed.len();
+        emitter.literal(&line[..start]);
+        emit_span(emitter, TokenKind::DocumentMarker, line_idx, start, trimmed);
         return;
     }
 
-    let indent = &line[..line.len() - line.trim_start().len()];
     let rest = line.trim_start();
+    emitter.literal(&line[..indent_len]);
 
-    // Directive (e.g. %YAML 1.2)
+    // Directive (e.g. %YAML 1.2) — ANSI-only concept, dimmed like a comment.
     if rest.starts_with('%') {
-        print!("{}", indent);
-        out.dim(rest, theme.line_number);
+        emit_span(emitter, TokenKind::Comment, line_idx, indent_len, rest);
         return;
     }
 
-    // List item: "- ..." or "-\n"
     if rest.starts_with("- ") || rest == "-" {
-        print!("{}", indent);
-        out.colored("- ", theme.list_bullet);
+        emit_span(emitter, TokenKind::ListBullet, line_idx, indent_len, "-");
+        emitter.literal(" ");
         if rest.len() > 2 {
-            let item = &rest[2..];
-            render_key_or_value(item, theme, out);
+            render_key_or_value_with_emitter(&rest[2..], line_idx, indent_len + 2, emitter);
         }
         return;
     }
 
-    // Key: value
-    if let Some(colon_pos) = find_colon(rest) {
-        print!("{}", indent);
-        let key = &rest[..colon_pos];
-        out.colored(key, theme.json_key);
-        out.colored(":", theme.json_key);
-
-        let after = &rest[colon_pos + 1..];
-        if after.is_empty() {
-            return;
-        }
+    render_key_or_value_with_emitter(rest, line_idx, indent_len, emitter);
+}
 
-        // Check for trailing comment
-        let (value_part, comment) = split_comment(after);
+/// Renders content that might be `key: value` or just a bare value —
+/// shared by top-level lines and the text following a `- ` list bullet.
+fn render_key_or_value_with_emitter(item: &str, line_idx: usize, col_start: usize, emitter: &mut dyn Emitter) {
+    let Some(colon_pos) = find_colon(item) else {
+        let (value_part, comment) = split_comment(item);
         let value_trimmed = value_part.trim();
-
-        if value_trimmed.is_empty() {
-            // Might just be spaces before a comment
-            if let Some(c) = comment {
-                print!(" ");
-                out.dim(c, theme.line_number);
-            }
-            return;
+        if !value_trimmed.is_empty() {
+            render_value_with_emitter(value_trimmed, line_idx, col_start, emitter);
         }
-
-        // Preserve the single space after colon
-        print!(" ");
-        render_typed_value(value_trimmed, theme, out);
-
         if let Some(c) = comment {
-            print!(" ");
-            out.dim(c, theme.line_number);
+            let comment_start = col_start + (item.len() - c.len());
+            emitter.literal(" ");
+            emit_span(emitter, TokenKind::Comment, line_idx, comment_start, c);
         }
         return;
-    }
+    };
 
-    // Bare value line (continuation, block scalar, etc.)
-    print!("{}", indent);
-    out.colored(rest, theme.text);
-}
+    let key = &item[..colon_pos];
+    emit_span(emitter, TokenKind::Key, line_idx, col_start, key);
+    emit_span(emitter, TokenKind::Key, line_idx, col_start + colon_pos, ":");
 
-/// Handle content after a list marker that might be `key: value` or just a value.
-fn render_key_or_value(item: &str, theme: &Theme, out: &Output) {
-    if let Some(colon_pos) = find_colon(item) {
-        let key = &item[..colon_pos];
-        out.colored(key, theme.json_key);
-        out.colored(":", theme.json_key);
-
-        let after = &item[colon_pos + 1..];
-        if after.is_empty() {
-            return;
-        }
-
-        let (value_part, comment) = split_comment(after);
-        let value_trimmed = value_part.trim();
-
-        if !value_trimmed.is_empty() {
-            print!(" ");
-            render_typed_value(value_trimmed, theme, out);
-        }
+    let after = &item[colon_pos + 1..];
+    if after.is_empty() {
+        return;
+    }
 
-        if let Some(c) = comment {
-            print!(" ");
-            out.dim(c, theme.line_number);
-        }
-    } else {
-        let (value_part, comment) = split_comment(item);
-        let value_trimmed = value_part.trim();
-        if !value_trimmed.is_empty() {
-            render_typed_value(value_trimmed, theme, out);
-        }
-        if let Some(c) = comment {
-            print!(" ");
-            out.dim(c, theme.line_number);
-        }
+    let (value_part, comment) = split_comment(after);
+    let value_trimmed = value_part.trim();
+    if !value_trimmed.is_empty() {
+        let value_offset = after.len() - after.trim_start().len();
+        emitter.literal(" ");
+        render_value_with_emitter(
+            value_trimmed,
+            line_idx,
+            col_start + colon_pos + 1 + value_offset,
+            emitter,
+        );
+    }
+    if let Some(c) = comment {
+        let comment_start = col_start + colon_pos + 1 + (after.len() - c.len());
+        emitter.literal(" ");
+        emit_span(emitter, TokenKind::Comment, line_idx, comment_start, c);
     }
 }
 
-fn render_typed_value(val: &str, theme: &Theme, out: &Output) {
+fn render_value_with_emitter(val: &str, line_idx: usize, col_start: usize, emitter: &mut dyn Emitter) {
     // Flow sequence [...] or mapping {...}
-    if (val.starts_with('[') && val.ends_with(']'))
-        || (val.starts_with('{') && val.ends_with('}'))
-    {
-        render_flow(val, theme, out);
+    if (val.starts_with('[') && val.ends_with(']')) || (val.starts_with('{') && val.ends_with('}')) {
+        render_flow_with_emitter(val, line_idx, col_start, emitter);
         return;
     }
 
     // Block scalar indicators
     if val == "|" || val == ">" || val == "|-" || val == ">-" || val == "|+" || val == ">+" {
-        out.colored(val, theme.json_bracket);
+        emit_span(emitter, TokenKind::Bracket, line_idx, col_start, val);
         return;
     }
 
     // Anchor/alias
     if val.starts_with('&') || val.starts_with('*') {
-        out.colored(val, theme.json_key);
+        emit_span(emitter, TokenKind::Key, line_idx, col_start, val);
         return;
     }
 
     // Tag
     if val.starts_with('!') {
-        out.colored(val, theme.json_bracket);
-        return;
-    }
-
-    let lower = val.to_lowercase();
-
-    // Boolean
-    if matches!(lower.as_str(), "true" | "false" | "yes" | "no" | "on" | "off") {
-        out.colored(val, theme.json_bool);
-        return;
-    }
-
-    // Null
-    if matches!(lower.as_str(), "null" | "~") {
-        out.colored(val, theme.json_null);
-        return;
-    }
-
-    // Quoted string
-    if (val.starts_with('"') && val.ends_with('"'))
-        || (val.starts_with('\'') && val.ends_with('\''))
-    {
-        out.colored(val, theme.json_string);
-        return;
-    }
-
-    // Number
-    if is_yaml_number(val) {
-        out.colored(val, theme.json_number);
+        emit_span(emitter, TokenKind::Bracket, line_idx, col_start, val);
         return;
     }
 
-    // Bare string
-    out.colored(val, theme.text);
+    emit_span(emitter, classify_value(val), line_idx, col_start, val);
 }
 
-fn render_flow(val: &str, theme: &Theme, out: &Output) {
-    let open = &val[..1];
-    let close = &val[val.len() - 1..];
-    let is_mapping = open == "{";
+fn render_flow_with_emitter(val: &str, line_idx: usize, col_start: usize, emitter: &mut dyn Emitter) {
+    let is_mapping = val.starts_with('{');
+    let mut cursor = col_start;
 
-    out.colored(open, theme.json_bracket);
+    emit_span(emitter, TokenKind::Bracket, line_idx, cursor, &val[..1]);
+    cursor += 1;
 
     let inner = &val[1..val.len() - 1];
     let parts = split_flow_top_level(inner);
 
     for (i, part) in parts.iter().enumerate() {
         if i > 0 {
-            out.colored(",", theme.json_bracket);
+            emit_span(emitter, TokenKind::Bracket, line_idx, cursor, ",");
+            cursor += 1;
         }
+        let leading = &part[..part.len() - part.trim_start().len()];
+        emitter.literal(leading);
+        cursor += leading.len();
+
         let trimmed = part.trim();
         if trimmed.is_empty() {
-            print!("{}", part);
             continue;
         }
-        let leading = &part[..part.len() - part.trim_start().len()];
-        print!("{}", leading);
 
         if is_mapping {
             if let Some(cp) = trimmed.find(':') {
                 let k = trimmed[..cp].trim();
-                out.colored(k, theme.json_key);
-                out.colored(":", theme.json_key);
+                emit_span(emitter, TokenKind::Key, line_idx, cursor, k);
+                cursor += k.len();
+                emit_span(emitter, TokenKind::Key, line_idx, cursor, ":");
+                cursor += 1;
                 let v = trimmed[cp + 1..].trim();
                 if !v.is_empty() {
-                    print!(" ");
-                    render_typed_value(v, theme, out);
+                    emitter.literal(" ");
+                    cursor += 1;
+                    render_value_with_emitter(v, line_idx, cursor, emitter);
+                    cursor += v.len();
                 }
             } else {
-                render_typed_value(trimmed, theme, out);
+                render_value_with_emitter(trimmed, line_idx, cursor, emitter);
+                cursor += trimmed.len();
             }
         } else {
-            render_typed_value(trimmed, theme, out);
+            render_value_with_emitter(trimmed, line_idx, cursor, emitter);
+            cursor += trimmed.len();
         }
+
+        let trailing = &part[leading.len() + trimmed.len()..];
+        emitter.literal(trailing);
+        cursor += trailing.len();
     }
 
-    out.colored(close, theme.json_bracket);
+    emit_span(emitter, TokenKind::Bracket, line_idx, cursor, &val[val.len() - 1..]);
+}
+
+fn emit_span(emitter: &mut dyn Emitter, kind: TokenKind, line: usize, col_start: usize, text: &str) {
+    emitter.emit(kind, Span { line, col_start, col_end: col_start + text.len() }, text);
+}
+
+/// Classifies a value into its displayed token kind (bool, null, number,
+/// quoted string, or bare string).
+fn classify_value(val: &str) -> TokenKind {
+    let lower = val.to_lowercase();
+    if matches!(lower.as_str(), "true" | "false" | "yes" | "no" | "on" | "off") {
+        return TokenKind::Bool;
+    }
+    if matches!(lower.as_str(), "null" | "~") {
+        return TokenKind::Null;
+    }
+    if (val.starts_with('"') && val.ends_with('"')) || (val.starts_with('\'') && val.ends_with('\'')) {
+        return TokenKind::StringValue;
+    }
+    if is_yaml_number(val) {
+        return TokenKind::Number;
+    }
+    TokenKind::BareString
 }
 
 /// Split by comma at top level (not inside strings, brackets, or braces).
@@ -280,7 +271,7 @@ fn split_flow_top_level(s: &str) -> Vec<&str> {
 
 /// Find the first `:` that is a YAML key separator (followed by space, end, or newline)
 /// and not inside a quoted string.
-fn find_colon(s: &str) -> Option<usize> {
+pub(crate) fn find_colon(s: &str) -> Option<usize> {
     let mut in_string = false;
     let mut quote_char = ' ';
     let bytes = s.as_bytes();
@@ -308,7 +299,7 @@ fn find_colon(s: &str) -> Option<usize> {
 }
 
 /// Split trailing `# comment` that's not inside a string.
-fn split_comment(value: &str) -> (&str, Option<&str>) {
+pub(crate) fn split_comment(value: &str) -> (&str, Option<&str>) {
     let mut in_string = false;
     let mut quote_char = ' ';
     let bytes = value.as_bytes();
@@ -0,0 +1,329 @@
+//! Per-language line counting for `--stats`: tokei-style code/comment/blank
+//! tallies, driven by a static comment-delimiter table keyed by the same
+//! language names `detect::detect_format` produces.
+//!
+//! Classification runs a small state machine over the raw characters
+//! (rather than per-line regexes) so a block comment or string literal that
+//! spans multiple lines, or a string containing a comment-like sequence
+//! (`"// not a comment"`), is handled correctly.
+
+/// Line-comment and block-comment delimiters for one language.
+#[derive(Debug, Clone, Copy)]
+struct CommentSyntax {
+    line: &'static [&'static str],
+    block: &'static [(&'static str, &'static str)],
+    /// Whether a block comment of this language can nest (only a handful,
+    /// like Rust's `/* */`, actually do).
+    nests: bool,
+}
+
+const fn syntax(line: &'static [&'static str], block: &'static [(&'static str, &'static str)]) -> CommentSyntax {
+    CommentSyntax { line, block, nests: false }
+}
+
+const fn nesting_syntax(
+    line: &'static [&'static str],
+    block: &'static [(&'static str, &'static str)],
+) -> CommentSyntax {
+    CommentSyntax { line, block, nests: true }
+}
+
+/// Comment delimiters for the languages `--stats` knows how to classify.
+/// Languages not listed here still get code/blank counts (everything
+/// non-blank counts as code), just no comment column.
+const LANGUAGES: &[(&str, CommentSyntax)] = &[
+    ("Rust", nesting_syntax(&["//"], &[("/*", "*/")])),
+    ("C", syntax(&["//"], &[("/*", "*/")])),
+    ("C++", syntax(&["//"], &[("/*", "*/")])),
+    ("Objective-C++", syntax(&["//"], &[("/*", "*/")])),
+    ("Java", syntax(&["//"], &[("/*", "*/")])),
+    ("C#", syntax(&["//"], &[("/*", "*/")])),
+    ("Go", syntax(&["//"], &[("/*", "*/")])),
+    ("Scala", syntax(&["//"], &[("/*", "*/")])),
+    ("Groovy", syntax(&["//"], &[("/*", "*/")])),
+    ("D", syntax(&["//"], &[("/*", "*/")])),
+    ("Zig", syntax(&["//"], &[])),
+    ("JavaScript", syntax(&["//"], &[("/*", "*/")])),
+    ("TypeScript", syntax(&["//"], &[("/*", "*/")])),
+    ("TSX", syntax(&["//"], &[("/*", "*/")])),
+    ("JSX", syntax(&["//"], &[("/*", "*/")])),
+    ("Swift", nesting_syntax(&["//"], &[("/*", "*/")])),
+    ("Kotlin", nesting_syntax(&["//"], &[("/*", "*/")])),
+    ("Dart", syntax(&["//"], &[("/*", "*/")])),
+    ("PHP", syntax(&["//", "#"], &[("/*", "*/")])),
+    ("CSS", syntax(&[], &[("/*", "*/")])),
+    ("SCSS", syntax(&["//"], &[("/*", "*/")])),
+    ("Protocol Buffers", syntax(&["//"], &[("/*", "*/")])),
+    ("GraphQL", syntax(&["#"], &[])),
+    ("Python", syntax(&["#"], &[("\"\"\"", "\"\"\""), ("'''", "'''")])),
+    ("Ruby", syntax(&["#"], &[("=begin", "=end")])),
+    ("Ruby Haml", syntax(&["#"], &[])),
+    ("Perl", syntax(&["#"], &[])),
+    ("Bash", syntax(&["#"], &[])),
+    ("Fish", syntax(&["#"], &[])),
+    ("PowerShell", syntax(&["#"], &[("<#", "#>")])),
+    ("Lua", syntax(&["--"], &[("--[[", "]]")])),
+    ("Haskell", nesting_syntax(&["--"], &[("{-", "-}")])),
+    ("OCaml", nesting_syntax(&[], &[("(*", "*)")])),
+    ("SQL", syntax(&["--"], &[("/*", "*/")])),
+    ("YAML", syntax(&["#"], &[])),
+    ("TOML", syntax(&["#"], &[])),
+    ("INI", syntax(&[";", "#"], &[])),
+    ("Makefile", syntax(&["#"], &[])),
+    ("CMake", syntax(&["#"], &[])),
+    ("Dockerfile", syntax(&["#"], &[])),
+    ("Terraform", syntax(&["#", "//"], &[("/*", "*/")])),
+    ("VimL", syntax(&["\""], &[])),
+    ("Elixir", syntax(&["#"], &[])),
+    ("Tcl", syntax(&["#"], &[])),
+    ("Batch File", syntax(&["REM", "::"], &[])),
+    ("HTML", syntax(&[], &[("<!--", "-->")])),
+    ("XML", syntax(&[], &[("<!--", "-->")])),
+    ("Markdown", syntax(&[], &[("<!--", "-->")])),
+];
+
+fn comment_syntax(lang: &str) -> Option<CommentSyntax> {
+    LANGUAGES
+        .iter()
+        .find(|(name, _)| *name == lang)
+        .map(|(_, syn)| *syn)
+}
+
+/// Code/comment/blank tallies for one file.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LineCounts {
+    pub code: usize,
+    pub comments: usize,
+    pub blanks: usize,
+}
+
+impl LineCounts {
+    pub fn total(&self) -> usize {
+        self.code + self.comments + self.blanks
+    }
+}
+
+/// Counts `content`'s lines as code/comment/blank for `lang`. Falls back to
+/// a comment-blind blank/code split for languages not in the delimiter
+/// table.
+pub fn count(content: &str, lang: &str) -> LineCounts {
+    match comment_syntax(lang) {
+        Some(syntax) => count_with_syntax(content, &syntax),
+        None => count_blank_only(content),
+    }
+}
+
+fn count_blank_only(content: &str) -> LineCounts {
+    let mut counts = LineCounts::default();
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            counts.blanks += 1;
+        } else {
+            counts.code += 1;
+        }
+    }
+    counts
+}
+
+fn count_with_syntax(content: &str, syntax: &CommentSyntax) -> LineCounts {
+    // Scanned as bytes, not chars: every delimiter/quote this table uses is
+    // ASCII, and treating a multi-byte UTF-8 sequence as several opaque
+    // "regular code" bytes classifies the line the same way a char-by-char
+    // scan would, without a full `Vec<char>` copy of the file.
+    let bytes = content.as_bytes();
+    let mut counts = LineCounts::default();
+
+    let mut line_has_char = false;
+    let mut line_has_code = false;
+    let mut line_has_comment = false;
+
+    let mut in_line_comment = false;
+    let mut in_string: Option<u8> = None;
+    let mut block_stack: Vec<&'static str> = Vec::new();
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+
+        if c == b'\n' {
+            flush_line(&mut counts, line_has_char, line_has_code, line_has_comment);
+            line_has_char = false;
+            line_has_code = false;
+            line_has_comment = false;
+            in_line_comment = false;
+            i += 1;
+            continue;
+        }
+
+        if !c.is_ascii_whitespace() {
+            line_has_char = true;
+        }
+
+        if in_line_comment {
+            line_has_comment = true;
+            i += 1;
+            continue;
+        }
+
+        if let Some(&close) = block_stack.last() {
+            line_has_comment = true;
+            if matches_at(bytes, i, close) {
+                block_stack.pop();
+                i += close.len();
+                continue;
+            }
+            if syntax.nests {
+                if let Some((open, close)) = syntax.block.iter().find(|(open, _)| matches_at(bytes, i, open)) {
+                    block_stack.push(close);
+                    i += open.len();
+                    continue;
+                }
+            }
+            i += 1;
+            continue;
+        }
+
+        if let Some(quote) = in_string {
+            line_has_code = true;
+            if c == b'\\' && bytes.get(i + 1).is_some_and(|&next| next != b'\n') {
+                i += 2;
+                continue;
+            }
+            if c == quote {
+                in_string = None;
+            }
+            i += 1;
+            continue;
+        }
+
+        // Comment markers are checked before a bare quote would start a
+        // string, so languages whose comment syntax itself begins with a
+        // quote character (Python's `"""`, VimL's `"`) are recognized as
+        // comments rather than being swallowed as string literals.
+        if let Some(marker) = syntax.line.iter().find(|m| matches_at(bytes, i, m)) {
+            in_line_comment = true;
+            line_has_comment = true;
+            i += marker.len();
+            continue;
+        }
+
+        if let Some((open, close)) = syntax.block.iter().find(|(open, _)| matches_at(bytes, i, open)) {
+            block_stack.push(close);
+            line_has_comment = true;
+            i += open.len();
+            continue;
+        }
+
+        if c == b'"' || c == b'\'' {
+            in_string = Some(c);
+            line_has_code = true;
+            i += 1;
+            continue;
+        }
+
+        line_has_code = true;
+        i += 1;
+    }
+
+    // `content` may not end with a trailing newline; flush whatever the
+    // last line accumulated.
+    if line_has_char {
+        flush_line(&mut counts, line_has_char, line_has_code, line_has_comment);
+    }
+
+    counts
+}
+
+fn flush_line(counts: &mut LineCounts, has_char: bool, has_code: bool, has_comment: bool) {
+    if !has_char {
+        counts.blanks += 1;
+    } else if has_code {
+        counts.code += 1;
+    } else if has_comment {
+        counts.comments += 1;
+    } else {
+        counts.blanks += 1;
+    }
+}
+
+/// Whether `pattern` occurs in `bytes` starting at index `i`.
+fn matches_at(bytes: &[u8], i: usize, pattern: &str) -> bool {
+    bytes[i..].starts_with(pattern.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_plain_code_and_blanks() {
+        let src = "fn main() {\n\n    println!(\"hi\");\n}\n";
+        let counts = count(src, "Rust");
+        assert_eq!(counts.code, 3);
+        assert_eq!(counts.blanks, 1);
+        assert_eq!(counts.comments, 0);
+    }
+
+    #[test]
+    fn test_count_line_comment() {
+        let src = "let x = 1; // set x\n// a whole comment line\n";
+        let counts = count(src, "Rust");
+        assert_eq!(counts.code, 1);
+        assert_eq!(counts.comments, 1);
+    }
+
+    #[test]
+    fn test_count_block_comment_spanning_lines() {
+        let src = "/*\n block comment\n still comment\n*/\nlet x = 1;\n";
+        let counts = count(src, "Rust");
+        assert_eq!(counts.comments, 4);
+        assert_eq!(counts.code, 1);
+    }
+
+    #[test]
+    fn test_count_nested_block_comment() {
+        let src = "/* outer /* inner */ still outer */\nlet x = 1;\n";
+        let counts = count(src, "Rust");
+        assert_eq!(counts.comments, 1);
+        assert_eq!(counts.code, 1);
+    }
+
+    #[test]
+    fn test_comment_marker_inside_string_is_code() {
+        let src = "let s = \"// not a comment\";\n";
+        let counts = count(src, "Rust");
+        assert_eq!(counts.code, 1);
+        assert_eq!(counts.comments, 0);
+    }
+
+    #[test]
+    fn test_unknown_language_falls_back_to_blank_split() {
+        let src = "one\n\ntwo\n";
+        let counts = count(src, "Whitespace");
+        assert_eq!(counts.code, 2);
+        assert_eq!(counts.blanks, 1);
+    }
+
+    #[test]
+    fn test_python_triple_quote_docstring_is_a_comment() {
+        let src = "\"\"\"\nmodule doc\n\"\"\"\nx = 1\n";
+        let counts = count(src, "Python");
+        assert_eq!(counts.comments, 3);
+        assert_eq!(counts.code, 1);
+    }
+
+    #[test]
+    fn test_vim_comment_marker_starting_with_quote() {
+        let src = "\" a vimscript comment\nlet x = 1\n";
+        let counts = count(src, "VimL");
+        assert_eq!(counts.comments, 1);
+        assert_eq!(counts.code, 1);
+    }
+
+    #[test]
+    fn test_backslash_newline_continuation_does_not_merge_lines() {
+        let src = "let s = \"line1\\\nline2\";\n";
+        let counts = count(src, "Rust");
+        assert_eq!(counts.code, 2);
+    }
+}
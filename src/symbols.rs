@@ -0,0 +1,167 @@
+//! Syntax-aware symbol extraction (feature = "treesitter")
+//!
+//! `brief_code` normally recognizes definitions with simple line-prefix
+//! heuristics (see `render::brief::keywords_for`), which breaks down on
+//! multi-line signatures, attributes, and indentation. When the crate is
+//! built with the `treesitter` feature, `extract_symbols` instead runs a
+//! tree-sitter query per language and returns real declaration nodes with
+//! their exact source span. Callers should treat `None` as "no grammar
+//! available for this language" and fall back to the keyword scanner.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Function,
+    Method,
+    Class,
+    Struct,
+    Enum,
+    Trait,
+    Interface,
+    Module,
+    Field,
+}
+
+impl SymbolKind {
+    /// Short glyph used by the text outline renderer (e.g. `fn`, `struct`).
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            SymbolKind::Function => "fn",
+            SymbolKind::Method => "fn",
+            SymbolKind::Class => "class",
+            SymbolKind::Struct => "struct",
+            SymbolKind::Enum => "enum",
+            SymbolKind::Trait => "trait",
+            SymbolKind::Interface => "iface",
+            SymbolKind::Module => "mod",
+            SymbolKind::Field => "·",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    /// 1-based line the declaration starts on.
+    pub line: usize,
+    /// 1-based line the declaration's body ends on.
+    pub end_line: usize,
+    pub kind: SymbolKind,
+    pub name: String,
+    /// The declaration's header, e.g. `pub async fn foo<T>(x: T) -> T`.
+    pub signature: String,
+}
+
+/// Runs a tree-sitter query for `lang` over `content` and returns the
+/// declarations it finds, or `None` if no grammar is wired up for `lang`
+/// (callers should fall back to the keyword-based scanner in that case).
+#[cfg(feature = "treesitter")]
+pub fn extract_symbols(content: &str, lang: &str) -> Option<Vec<Symbol>> {
+    use tree_sitter::{Parser, Query, QueryCursor};
+
+    let (language, query_src) = grammar_for(lang)?;
+
+    let mut parser = Parser::new();
+    parser.set_language(language).ok()?;
+    let tree = parser.parse(content, None)?;
+
+    let query = Query::new(language, query_src).ok()?;
+    let mut cursor = QueryCursor::new();
+    let bytes = content.as_bytes();
+
+    let mut symbols = Vec::new();
+    for m in cursor.matches(&query, tree.root_node(), bytes) {
+        let mut name = String::new();
+        let mut decl_node = None;
+
+        for cap in m.captures {
+            let cap_name = &query.capture_names()[cap.index as usize];
+            match cap_name.as_str() {
+                "name" => name = cap.node.utf8_text(bytes).unwrap_or("").to_string(),
+                "decl" => decl_node = Some(cap.node),
+                _ => {}
+            }
+        }
+
+        let Some(node) = decl_node else { continue };
+        let kind = kind_for_node(node.kind());
+        let start = node.start_position().row + 1;
+        let end = node.end_position().row + 1;
+        let header_end = node.start_position().row.min(node.end_position().row);
+        let signature = content
+            .lines()
+            .nth(header_end)
+            .unwrap_or("")
+            .trim()
+            .to_string();
+
+        symbols.push(Symbol {
+            line: start,
+            end_line: end,
+            kind,
+            name,
+            signature,
+        });
+    }
+
+    symbols.sort_by_key(|s| s.line);
+    Some(symbols)
+}
+
+#[cfg(not(feature = "treesitter"))]
+pub fn extract_symbols(_content: &str, _lang: &str) -> Option<Vec<Symbol>> {
+    None
+}
+
+#[cfg(feature = "treesitter")]
+fn kind_for_node(node_kind: &str) -> SymbolKind {
+    match node_kind {
+        "function_item" | "function_definition" | "function_declaration" => SymbolKind::Function,
+        "method_declaration" | "method_definition" => SymbolKind::Method,
+        "struct_item" | "struct_specifier" => SymbolKind::Struct,
+        "enum_item" | "enum_specifier" => SymbolKind::Enum,
+        "trait_item" => SymbolKind::Trait,
+        "interface_declaration" => SymbolKind::Interface,
+        "class_declaration" | "class_definition" => SymbolKind::Class,
+        "mod_item" | "module" | "namespace_definition" => SymbolKind::Module,
+        _ => SymbolKind::Function,
+    }
+}
+
+#[cfg(feature = "treesitter")]
+fn grammar_for(lang: &str) -> Option<(tree_sitter::Language, &'static str)> {
+    match lang.to_lowercase().as_str() {
+        "rust" => Some((
+            tree_sitter_rust::language(),
+            "[
+                (function_item name: (identifier) @name) @decl
+                (struct_item name: (type_identifier) @name) @decl
+                (enum_item name: (type_identifier) @name) @decl
+                (trait_item name: (type_identifier) @name) @decl
+                (mod_item name: (identifier) @name) @decl
+            ]",
+        )),
+        "python" => Some((
+            tree_sitter_python::language(),
+            "[
+                (function_definition name: (identifier) @name) @decl
+                (class_definition name: (identifier) @name) @decl
+            ]",
+        )),
+        "javascript" | "typescript" => Some((
+            tree_sitter_javascript::language(),
+            "[
+                (function_declaration name: (identifier) @name) @decl
+                (class_declaration name: (identifier) @name) @decl
+                (method_definition name: (property_identifier) @name) @decl
+            ]",
+        )),
+        "go" => Some((
+            tree_sitter_go::language(),
+            "[
+                (function_declaration name: (identifier) @name) @decl
+                (method_declaration name: (field_identifier) @name) @decl
+                (type_declaration (type_spec name: (type_identifier) @name)) @decl
+            ]",
+        )),
+        _ => None,
+    }
+}